@@ -0,0 +1,89 @@
+use crate::HttpMessage;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri, Version};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Generates requests and responses that survive a serialize / parse-back round trip: HTTP/1.x,
+/// origin-form targets, `x-` headers without surrounding whitespace, and arbitrary body bytes.
+/// Statuses whose responses never carry a body (1xx, 204, 304) are left out.
+pub fn arb_message() -> impl Strategy<Value = HttpMessage> {
+    let version = prop_oneof![Just(Version::HTTP_10), Just(Version::HTTP_11)];
+    let headers = vec(("x-[a-z]{1,10}", "[!-~]([ -~]{0,20}[!-~])?"), 0..5).prop_map(|pairs| {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            let name = HeaderName::from_bytes(name.as_bytes()).unwrap();
+            headers.append(name, HeaderValue::from_str(&value).unwrap());
+        }
+        headers
+    });
+    let body = vec(any::<u8>(), 0..64);
+    let method = prop_oneof![
+        Just(Method::GET),
+        Just(Method::POST),
+        Just(Method::PUT),
+        Just(Method::DELETE),
+        "[A-Z]{1,8}".prop_map(|method| Method::from_bytes(method.as_bytes()).unwrap()),
+    ];
+    let request = (
+        method,
+        "/[a-z0-9/]{0,20}(\\?[a-z0-9=&]{1,10})?".prop_map(|uri| uri.parse::<Uri>().unwrap()),
+        version.clone(),
+        headers.clone(),
+        body.clone(),
+    )
+        .prop_map(|(method, uri, version, headers, body)| {
+            let mut request = Request::new(body);
+            *request.method_mut() = method;
+            *request.uri_mut() = uri;
+            *request.version_mut() = version;
+            *request.headers_mut() = headers;
+            HttpMessage::Request(request)
+        });
+    let response = (
+        (200u16..600).prop_filter("body-less status", |status| !matches!(status, 204 | 304)),
+        version,
+        headers,
+        body,
+    )
+        .prop_map(|(status, version, headers, body)| {
+            let mut response = Response::new(body);
+            *response.status_mut() = StatusCode::from_u16(status).unwrap();
+            *response.version_mut() = version;
+            *response.headers_mut() = headers;
+            HttpMessage::Response(response)
+        });
+    prop_oneof![request, response]
+}
+/// Serializes `message`, parses the bytes back and panics unless every part matches.
+pub fn assert_roundtrip(message: &HttpMessage) {
+    let bytes = message.parser().parse_bytes().expect("message serializes");
+    let parsed = HttpMessage::try_from(&bytes[..]).expect("serialized message parses back");
+    match (message, &parsed) {
+        (HttpMessage::Request(expected), HttpMessage::Request(actual)) => {
+            assert_eq!(actual.method(), expected.method());
+            assert_eq!(actual.uri(), expected.uri());
+            assert_eq!(actual.version(), expected.version());
+            assert_eq!(actual.headers(), expected.headers());
+            assert_eq!(actual.body(), expected.body());
+        }
+        (HttpMessage::Response(expected), HttpMessage::Response(actual)) => {
+            assert_eq!(actual.status(), expected.status());
+            assert_eq!(actual.version(), expected.version());
+            assert_eq!(actual.headers(), expected.headers());
+            assert_eq!(actual.body(), expected.body());
+        }
+        _ => panic!("round trip changed the message kind: {message:?} became {parsed:?}"),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{arb_message, assert_roundtrip};
+    use proptest::proptest;
+
+    proptest! {
+        #[test]
+        fn messages_round_trip(message in arb_message()) {
+            assert_roundtrip(&message);
+        }
+    }
+}
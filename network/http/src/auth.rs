@@ -0,0 +1,59 @@
+use crate::ParseError;
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::ToString;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// An RFC 7617 `Authorization: Basic` value, with the credentials encoded as UTF-8.
+/// The user-id can't contain a colon, since the receiver splits on the first one.
+pub fn basic_auth(user: &str, pass: &str) -> Result<HeaderValue, ParseError> {
+    if user.contains(':') {
+        return Err(ParseError::InvalidHeaderValue(header::AUTHORIZATION.to_string()));
+    }
+    let credentials = STANDARD.encode(format!("{user}:{pass}"));
+    HeaderValue::from_str(&format!("Basic {credentials}"))
+        .map_err(|_| ParseError::InvalidHeaderValue(header::AUTHORIZATION.to_string()))
+}
+/// An RFC 6750 `Authorization: Bearer` value; `token` must match the token68 grammar.
+pub fn bearer_auth(token: &str) -> Result<HeaderValue, ParseError> {
+    let body = token.trim_end_matches('=');
+    let valid = !body.is_empty()
+        && body.bytes().all(|b| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b));
+    if !valid {
+        return Err(ParseError::InvalidHeaderValue(header::AUTHORIZATION.to_string()));
+    }
+    HeaderValue::from_str(&format!("Bearer {token}"))
+        .map_err(|_| ParseError::InvalidHeaderValue(header::AUTHORIZATION.to_string()))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{basic_auth, bearer_auth, HttpParser, ParseError};
+    use http::{header, Request};
+
+    #[test]
+    fn basic_auth_matches_rfc_example() {
+        assert_eq!(basic_auth("Aladdin", "open sesame").unwrap(), "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert_eq!(basic_auth("test", "123£").unwrap(), "Basic dGVzdDoxMjPCow==");
+        assert_eq!(basic_auth("user", "pa:ss").unwrap(), "Basic dXNlcjpwYTpzcw==");
+        assert_eq!(
+            basic_auth("us:er", "pass").unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("authorization"))
+        );
+    }
+    #[test]
+    fn bearer_auth_attaches_to_request() {
+        let request = Request::builder()
+            .uri("/")
+            .header(header::HOST, "example.com")
+            .header(header::AUTHORIZATION, bearer_auth("mF_9.B5f-4.1JqM").unwrap())
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse().unwrap(),
+            "GET / HTTP/1.1\r\nhost:example.com\r\nauthorization:Bearer mF_9.B5f-4.1JqM\r\n\r\n"
+        );
+        assert!(bearer_auth("has space").is_err());
+        assert!(bearer_auth("").is_err());
+    }
+}
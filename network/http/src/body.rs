@@ -0,0 +1,39 @@
+use http::{Request, Response};
+
+/// Replaces the body with `f(body)`, keeping method, URI, version, headers and extensions.
+/// Fix up `Content-Length` afterwards if `f` changes the body's length.
+pub fn map_body<T, U>(msg: Request<T>, f: impl FnOnce(T) -> U) -> Request<U> {
+    let (parts, body) = msg.into_parts();
+    Request::from_parts(parts, f(body))
+}
+/// `map_body` for responses: keeps status, version, headers and extensions.
+pub fn map_response_body<T, U>(msg: Response<T>, f: impl FnOnce(T) -> U) -> Response<U> {
+    let (parts, body) = msg.into_parts();
+    Response::from_parts(parts, f(body))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{map_body, map_response_body, HttpParser};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn map_body_keeps_headers() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/echo")
+            .version(Version::HTTP_10)
+            .header(header::CONTENT_LENGTH, "5")
+            .body(String::from("hello"))
+            .unwrap();
+        let mapped = map_body(request, |body| body.to_uppercase());
+        assert_eq!(
+            HttpParser::from_request(&mapped).parse().unwrap(),
+            "POST /echo HTTP/1.0\r\ncontent-length:5\r\n\r\nHELLO"
+        );
+
+        let response = Response::builder().status(StatusCode::OK).body("<p> hi </p>").unwrap();
+        let mapped = map_response_body(response, |body| body.replace(' ', "").into_bytes());
+        assert_eq!(mapped.status(), StatusCode::OK);
+        assert_eq!(mapped.body(), b"<p>hi</p>");
+    }
+}
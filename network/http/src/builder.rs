@@ -0,0 +1,164 @@
+use crate::{HttpMessage, ParseError};
+use http::header::{HeaderName, HeaderValue};
+use http::{request, response, Method, Request, Response, StatusCode, Uri, Version};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// Wraps the `http` builders and runs the `parse_strict()` checks, plus Content-Length and
+/// HTTP/2 pseudo-header checks, when the body is set rather than at serialization time.
+#[derive(Debug)]
+pub struct MessageBuilder {
+    kind: BuilderKind,
+    error: Option<ParseError>,
+}
+#[derive(Debug)]
+enum BuilderKind {
+    Request(request::Builder),
+    Response(response::Builder),
+}
+impl MessageBuilder {
+    pub fn request() -> Self {
+        Self::new(BuilderKind::Request(Request::builder()))
+    }
+    pub fn response() -> Self {
+        Self::new(BuilderKind::Response(Response::builder()))
+    }
+    fn new(kind: BuilderKind) -> Self {
+        MessageBuilder { kind, error: None }
+    }
+    pub fn method<M>(mut self, method: M) -> Self
+    where
+        M: TryInto<Method>,
+        M::Error: Into<http::Error>,
+    {
+        self.kind = match self.kind {
+            BuilderKind::Request(builder) => BuilderKind::Request(builder.method(method)),
+            kind => {
+                self.error.get_or_insert(ParseError::WrongVariant);
+                kind
+            }
+        };
+        self
+    }
+    pub fn uri<U>(mut self, uri: U) -> Self
+    where
+        U: TryInto<Uri>,
+        U::Error: Into<http::Error>,
+    {
+        self.kind = match self.kind {
+            BuilderKind::Request(builder) => BuilderKind::Request(builder.uri(uri)),
+            kind => {
+                self.error.get_or_insert(ParseError::WrongVariant);
+                kind
+            }
+        };
+        self
+    }
+    pub fn status<S>(mut self, status: S) -> Self
+    where
+        S: TryInto<StatusCode>,
+        S::Error: Into<http::Error>,
+    {
+        self.kind = match self.kind {
+            BuilderKind::Response(builder) => BuilderKind::Response(builder.status(status)),
+            kind => {
+                self.error.get_or_insert(ParseError::WrongVariant);
+                kind
+            }
+        };
+        self
+    }
+    pub fn version(mut self, version: Version) -> Self {
+        self.kind = match self.kind {
+            BuilderKind::Request(builder) => BuilderKind::Request(builder.version(version)),
+            BuilderKind::Response(builder) => BuilderKind::Response(builder.version(version)),
+        };
+        self
+    }
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Into<http::Error>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<http::Error>,
+    {
+        self.kind = match self.kind {
+            BuilderKind::Request(builder) => BuilderKind::Request(builder.header(name, value)),
+            BuilderKind::Response(builder) => BuilderKind::Response(builder.header(name, value)),
+        };
+        self
+    }
+    /// Sets the body and checks the finished message, so an invalid one never reaches `parse()`.
+    pub fn body(self, body: impl Into<Vec<u8>>) -> Result<HttpMessage, ParseError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let body = body.into();
+        let invalid = |error: http::Error| ParseError::InvalidMessage(error.to_string());
+        let message = match self.kind {
+            BuilderKind::Request(builder) => HttpMessage::Request(builder.body(body).map_err(invalid)?),
+            BuilderKind::Response(builder) => HttpMessage::Response(builder.body(body).map_err(invalid)?),
+        };
+        let parser = message.parser();
+        parser.check_host()?;
+        parser.check_framing()?;
+        parser.check_pseudo_headers()?;
+        parser.check_content_length()?;
+        Ok(message)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpMessage, MessageBuilder, ParseError};
+    use http::{header, Method, StatusCode, Version};
+
+    #[test]
+    fn builds_valid_messages() {
+        let request = MessageBuilder::request()
+            .method(Method::POST)
+            .uri("/submit")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "example.com")
+            .header(header::CONTENT_LENGTH, "2")
+            .body("hi")
+            .unwrap();
+        assert!(matches!(request, HttpMessage::Request(_)));
+        assert_eq!(
+            request.to_string(),
+            "POST /submit HTTP/1.1\r\nhost:example.com\r\ncontent-length:2\r\n\r\nhi"
+        );
+
+        let response = MessageBuilder::response().status(StatusCode::NO_CONTENT).body(Vec::new()).unwrap();
+        assert!(matches!(response, HttpMessage::Response(_)));
+    }
+    #[test]
+    fn rejects_body_with_zero_content_length() {
+        let error = MessageBuilder::request()
+            .method(Method::GET)
+            .uri("/")
+            .header(header::HOST, "example.com")
+            .header(header::CONTENT_LENGTH, "0")
+            .body("unexpected")
+            .unwrap_err();
+        assert_eq!(error, ParseError::ContentLengthMismatch { declared: 0, actual: 10 });
+    }
+    #[test]
+    fn rejects_missing_host_and_pseudo_headers() {
+        let error = MessageBuilder::request().uri("/").version(Version::HTTP_11).body("").unwrap_err();
+        assert_eq!(error, ParseError::MissingHost);
+
+        let error = MessageBuilder::request().uri("/").version(Version::HTTP_2).body("").unwrap_err();
+        assert_eq!(error, ParseError::MissingPseudoHeader(String::from(":authority")));
+
+        let request = MessageBuilder::request().uri("https://example.com/").version(Version::HTTP_2).body("");
+        assert!(request.is_ok());
+    }
+    #[test]
+    fn rejects_wrong_variant_and_invalid_parts() {
+        let error = MessageBuilder::response().method(Method::GET).body("").unwrap_err();
+        assert_eq!(error, ParseError::WrongVariant);
+
+        let error = MessageBuilder::request().uri("/").header("bad header", "x").body("").unwrap_err();
+        assert!(matches!(error, ParseError::InvalidMessage(_)));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{headers, uri_form, HttpParser, Message, ParseError};
+use http::{header, HeaderName};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+impl<T> HttpParser<'_, T> {
+    /// A deterministic cache key for a request: the method, the lowercased scheme and host, the
+    /// normalized path and sorted query, then a `name:value` line per `vary` header, by name.
+    /// Hop-by-hop headers never take part; absent headers contribute an empty value.
+    pub fn cache_key(&self, vary: &[HeaderName]) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let uri = request.uri();
+        let host = match uri_form::host_of(uri) {
+            Some(host) => host.to_ascii_lowercase(),
+            None => request.headers()
+                .get(header::HOST)
+                .map(|host| String::from_utf8_lossy(host.as_bytes()).to_ascii_lowercase())
+                .unwrap_or_default(),
+        };
+        let scheme = uri.scheme_str().unwrap_or("http").to_ascii_lowercase();
+        let mut key = format!("{} {scheme}://{host}{}", request.method(), uri_form::cache_target(uri));
+        let mut vary = vary.iter()
+            .filter(|name| !headers::HOP_BY_HOP.contains(name))
+            .collect::<Vec<_>>();
+        vary.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        vary.dedup();
+        for name in vary {
+            let values = request.headers()
+                .get_all(name)
+                .iter()
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).trim().to_string())
+                .collect::<Vec<_>>();
+            key.push_str(&format!("\n{name}:{}", values.join(", ")));
+        }
+        Ok(key)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, Request, Response};
+
+    #[test]
+    fn cache_key_ignores_order_and_spelling() {
+        let a = Request::builder()
+            .uri("/a/./b/../c?z=1&a=2")
+            .header(header::HOST, "Example.COM")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .header(header::ACCEPT_LANGUAGE, "en")
+            .header(header::CONNECTION, "close")
+            .body("")
+            .unwrap();
+        let b = Request::builder()
+            .uri("http://example.com/a/c?a=2&z=1")
+            .header(header::ACCEPT_LANGUAGE, "en")
+            .header(header::ACCEPT_ENCODING, " gzip ")
+            .body("")
+            .unwrap();
+        let vary = [header::ACCEPT_LANGUAGE, header::ACCEPT_ENCODING, header::CONNECTION];
+        let key = HttpParser::from_request(&a).cache_key(&vary).unwrap();
+        assert_eq!(key, "GET http://example.com/a/c?a=2&z=1\naccept-encoding:gzip\naccept-language:en");
+        assert_eq!(HttpParser::from_request(&b).cache_key(&vary).unwrap(), key);
+
+        let different = Request::builder().uri("http://example.com/a/c?a=2&z=1").body("").unwrap();
+        assert_ne!(HttpParser::from_request(&different).cache_key(&vary).unwrap(), key);
+
+        let response = Response::builder().body("").unwrap();
+        assert_eq!(HttpParser::from_response(&response).cache_key(&[]).unwrap_err(), ParseError::WrongVariant);
+    }
+}
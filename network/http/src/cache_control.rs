@@ -0,0 +1,150 @@
+use crate::{headers, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Builds a `Cache-Control` value (RFC 9111 §5.2.2). Directives are written in a fixed order:
+/// `public`/`private`, `no-cache`, `no-store`, `no-transform`, `must-revalidate`,
+/// `proxy-revalidate`, `immutable`, `max-age`, `s-maxage`, `stale-while-revalidate`, `stale-if-error`.
+#[derive(Clone, Debug, Default)]
+pub struct CacheControl {
+    public: bool,
+    private: Option<Vec<String>>,
+    no_cache: bool,
+    no_store: bool,
+    no_transform: bool,
+    must_revalidate: bool,
+    proxy_revalidate: bool,
+    immutable: bool,
+    max_age: Option<u64>,
+    s_maxage: Option<u64>,
+    stale_while_revalidate: Option<u64>,
+    stale_if_error: Option<u64>,
+}
+impl CacheControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+    pub fn private(mut self) -> Self {
+        self.private.get_or_insert_with(Vec::new);
+        self
+    }
+    /// `private="a, b"`: only the named header fields are private.
+    pub fn private_fields(mut self, fields: &[&str]) -> Self {
+        self.private.get_or_insert_with(Vec::new).extend(fields.iter().map(|field| field.to_string()));
+        self
+    }
+    pub fn no_cache(mut self) -> Self {
+        self.no_cache = true;
+        self
+    }
+    pub fn no_store(mut self) -> Self {
+        self.no_store = true;
+        self
+    }
+    pub fn no_transform(mut self) -> Self {
+        self.no_transform = true;
+        self
+    }
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+    pub fn proxy_revalidate(mut self) -> Self {
+        self.proxy_revalidate = true;
+        self
+    }
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+    pub fn s_maxage(mut self, seconds: u64) -> Self {
+        self.s_maxage = Some(seconds);
+        self
+    }
+    pub fn stale_while_revalidate(mut self, seconds: u64) -> Self {
+        self.stale_while_revalidate = Some(seconds);
+        self
+    }
+    pub fn stale_if_error(mut self, seconds: u64) -> Self {
+        self.stale_if_error = Some(seconds);
+        self
+    }
+    /// `public` and `private` together are contradictory, and `private` field names must be tokens.
+    pub fn build(&self) -> Result<HeaderValue, ParseError> {
+        let invalid = || ParseError::InvalidHeaderValue(header::CACHE_CONTROL.to_string());
+        let mut directives = Vec::new();
+        match (&self.private, self.public) {
+            (Some(_), true) => return Err(invalid()),
+            (Some(fields), false) if fields.is_empty() => directives.push(String::from("private")),
+            (Some(fields), false) => {
+                if !fields.iter().all(|field| headers::is_token(field)) {
+                    return Err(invalid());
+                }
+                directives.push(format!("private=\"{}\"", fields.join(", ")));
+            }
+            (None, true) => directives.push(String::from("public")),
+            (None, false) => {}
+        }
+        let flags = [
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+            (self.no_transform, "no-transform"),
+            (self.must_revalidate, "must-revalidate"),
+            (self.proxy_revalidate, "proxy-revalidate"),
+            (self.immutable, "immutable"),
+        ];
+        directives.extend(flags.into_iter().filter(|(set, _)| *set).map(|(_, name)| String::from(name)));
+        let durations = [
+            (self.max_age, "max-age"),
+            (self.s_maxage, "s-maxage"),
+            (self.stale_while_revalidate, "stale-while-revalidate"),
+            (self.stale_if_error, "stale-if-error"),
+        ];
+        for (seconds, name) in durations {
+            if let Some(seconds) = seconds {
+                directives.push(format!("{name}={seconds}"));
+            }
+        }
+        HeaderValue::from_str(&directives.join(", ")).map_err(|_| invalid())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{CacheControl, HttpParser, ParseError};
+    use http::{header, Response, StatusCode};
+
+    #[test]
+    fn cache_control_renders_in_stable_order() {
+        let value = CacheControl::new().must_revalidate().max_age(3600).public().build().unwrap();
+        assert_eq!(value, "public, must-revalidate, max-age=3600");
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CACHE_CONTROL, value)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 200 OK\r\ncache-control:public, must-revalidate, max-age=3600\r\n\r\n"
+        );
+
+        let value = CacheControl::new().s_maxage(60).no_store().private_fields(&["set-cookie", "x-user"]).build().unwrap();
+        assert_eq!(value, "private=\"set-cookie, x-user\", no-store, s-maxage=60");
+        assert_eq!(CacheControl::new().private().no_cache().build().unwrap(), "private, no-cache");
+    }
+    #[test]
+    fn cache_control_rejects_contradictions() {
+        let invalid = ParseError::InvalidHeaderValue(String::from("cache-control"));
+        assert_eq!(CacheControl::new().public().private().build().unwrap_err(), invalid);
+        assert_eq!(CacheControl::new().private_fields(&["bad field"]).build().unwrap_err(), invalid);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{HttpParser, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+/// Fixed values `parse_canonical` puts in place of the volatile parts of a message.
+#[derive(Clone, Debug, Default)]
+pub struct CanonicalOverrides {
+    /// Replaces an existing `Date` header.
+    pub date: Option<HeaderValue>,
+    /// Replaces an existing `ETag` header.
+    pub etag: Option<HeaderValue>,
+    /// Replaces the `Content-Type` boundary and every delimiter built from it in the body.
+    pub boundary: Option<String>,
+}
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes with the volatile headers and multipart boundary swapped for `overrides`, so
+    /// golden tests get the same output on every run. Headers the message lacks are not added,
+    /// and a `Content-Length` follows the body if the new boundary changes its size.
+    pub fn parse_canonical(&self, overrides: CanonicalOverrides) -> Result<String, ParseError> {
+        let mut headers = self.headers().clone();
+        for (name, value) in [(header::DATE, overrides.date), (header::ETAG, overrides.etag)] {
+            if let Some(value) = value.filter(|_| headers.contains_key(&name)) {
+                headers.insert(name, value);
+            }
+        }
+        let mut body = self.body_string();
+        let media_type = self.content_type().filter(|media_type| media_type.boundary().is_some());
+        if let (Some(mut media_type), Some(new)) = (media_type, overrides.boundary) {
+            let old = media_type.params.insert(String::from("boundary"), new.clone()).unwrap_or_default();
+            let content_type = HeaderValue::from_str(&media_type.to_string())
+                .map_err(|_| ParseError::InvalidHeaderValue(header::CONTENT_TYPE.to_string()))?;
+            headers.insert(header::CONTENT_TYPE, content_type);
+            body = replace_delimiters(&body, &old, &new);
+            if headers.contains_key(header::CONTENT_LENGTH) {
+                headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+            }
+        }
+        let head = self.assemble_head(&self.parse_start_line()?, &headers);
+        Ok(Self::join_head(head, &body))
+    }
+}
+/// Rewrites only the delimiter lines of a multipart body (`--old` and the closing `--old--`,
+/// with optional trailing whitespace); content that merely contains `--old` is kept.
+fn replace_delimiters(body: &str, old: &str, new: &str) -> String {
+    body.split("\r\n")
+        .map(|line| {
+            let rest = line.strip_prefix("--").and_then(|line| line.strip_prefix(old));
+            match rest {
+                Some(rest) if rest.strip_prefix("--").unwrap_or(rest).trim_end_matches([' ', '\t']).is_empty() => {
+                    format!("--{new}{rest}")
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+#[cfg(test)]
+mod tests {
+    use crate::{CanonicalOverrides, HttpParser};
+    use http::{header, HeaderValue, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_canonical_fixes_date_and_etag() {
+        let response = |date: &str| {
+            Response::builder()
+                .status(StatusCode::OK)
+                .version(Version::HTTP_11)
+                .header(header::DATE, date)
+                .header(header::ETAG, format!("\"{}\"", date.len()))
+                .body("hi")
+                .unwrap()
+        };
+        let overrides = || CanonicalOverrides {
+            date: Some(HeaderValue::from_static("Thu, 01 Jan 1970 00:00:00 GMT")),
+            etag: Some(HeaderValue::from_static("\"fixed\"")),
+            ..CanonicalOverrides::default()
+        };
+        let first = response("Mon, 12 Oct 2026 08:00:00 GMT");
+        let second = response("Wed, 14 Oct 2026 21:15:09 GMT");
+        let first = HttpParser::from_response(&first).parse_canonical(overrides()).unwrap();
+        assert_eq!(first, HttpParser::from_response(&second).parse_canonical(overrides()).unwrap());
+        assert_eq!(
+            first,
+            "HTTP/1.1 200 OK\r\ndate:Thu, 01 Jan 1970 00:00:00 GMT\r\netag:\"fixed\"\r\n\r\nhi"
+        );
+
+        let undated = Response::builder().status(StatusCode::OK).body("hi").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&undated).parse_canonical(overrides()).unwrap(),
+            "HTTP/1.1 200 OK\r\n\r\nhi"
+        );
+    }
+    #[test]
+    fn parse_canonical_replaces_boundary() {
+        let body = "--x7f3a\r\ncontent-disposition: form-data; name=\"a\"\r\n\r\n1\r\n--x7f3a--\r\n";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "multipart/form-data; boundary=x7f3a")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(body)
+            .unwrap();
+        let overrides = CanonicalOverrides { boundary: Some(String::from("fixed-boundary")), ..CanonicalOverrides::default() };
+        let canonical = HttpParser::from_response(&response).parse_canonical(overrides).unwrap();
+        let expected_body = body.replace("x7f3a", "fixed-boundary");
+        assert_eq!(
+            canonical,
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type:multipart/form-data; boundary=fixed-boundary\r\ncontent-length:{}\r\n\r\n{expected_body}",
+                expected_body.len()
+            )
+        );
+        assert!(!canonical.contains("x7f3a"));
+    }
+    #[test]
+    fn parse_canonical_replaces_only_the_boundary() {
+        let body = "--form\r\nx-note: --formal\r\n\r\nform-data --form\r\n--form--\r\n";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "multipart/form-data; boundary=form")
+            .body(body)
+            .unwrap();
+        let overrides = CanonicalOverrides { boundary: Some(String::from("fixed")), ..CanonicalOverrides::default() };
+        assert_eq!(
+            HttpParser::from_response(&response).parse_canonical(overrides).unwrap(),
+            "HTTP/1.1 200 OK\r\ncontent-type:multipart/form-data; boundary=fixed\r\n\r\n\
+             --fixed\r\nx-note: --formal\r\n\r\nform-data --form\r\n--fixed--\r\n"
+        );
+    }
+}
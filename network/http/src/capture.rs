@@ -0,0 +1,72 @@
+use crate::{HttpMessage, HttpParser, ParseError};
+use alloc::format;
+use alloc::vec::Vec;
+
+const PREFIX_LEN: usize = 4;
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// The `parse_bytes()` output behind a 4-byte big-endian length, so a capture file can hold
+    /// many messages back to back. Read them with `decode_framed`.
+    pub fn encode_framed(&self) -> Result<Vec<u8>, ParseError> {
+        let bytes = self.parse_bytes()?;
+        let len = u32::try_from(bytes.len())
+            .map_err(|_| ParseError::InvalidMessage(format!("{} bytes do not fit a frame", bytes.len())))?;
+        let mut framed = Vec::with_capacity(PREFIX_LEN + bytes.len());
+        framed.extend_from_slice(&len.to_be_bytes());
+        framed.extend_from_slice(&bytes);
+        Ok(framed)
+    }
+}
+/// Reads one frame written by `encode_framed` from the start of `buf`, returning the message
+/// and the number of bytes consumed.
+pub fn decode_framed(buf: &[u8]) -> Result<(HttpMessage, usize), ParseError> {
+    let truncated = || ParseError::InvalidMessage(format!("truncated frame of {} bytes", buf.len()));
+    let prefix = buf.first_chunk::<PREFIX_LEN>().ok_or_else(truncated)?;
+    let end = PREFIX_LEN + u32::from_be_bytes(*prefix) as usize;
+    let frame = buf.get(PREFIX_LEN..end).ok_or_else(truncated)?;
+    Ok((HttpMessage::try_from(frame)?, end))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{decode_framed, HttpMessage, HttpParser};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn framed_round_trip() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/upload")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "example.com")
+            .header(header::CONTENT_LENGTH, "3")
+            .body(vec![0x00, 0xff, 0x10])
+            .unwrap();
+        let response = Response::builder()
+            .status(StatusCode::CREATED)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "2")
+            .body(b"ok".to_vec())
+            .unwrap();
+        let mut capture = HttpParser::from_request(&request).encode_framed().unwrap();
+        let first_len = capture.len();
+        assert_eq!(&capture[..4], &(first_len as u32 - 4).to_be_bytes());
+        capture.extend(HttpParser::from_response(&response).encode_framed().unwrap());
+
+        let (first, consumed) = decode_framed(&capture).unwrap();
+        assert_eq!(consumed, first_len);
+        let HttpMessage::Request(parsed) = &first else {
+            panic!("expected a request");
+        };
+        assert_eq!(parsed.uri(), "/upload");
+        assert_eq!(parsed.body(), request.body());
+
+        let (second, consumed) = decode_framed(&capture[first_len..]).unwrap();
+        assert_eq!(first_len + consumed, capture.len());
+        assert_eq!(second.to_string(), "HTTP/1.1 201 Created\r\ncontent-length:2\r\n\r\nok");
+    }
+    #[test]
+    fn decode_framed_rejects_truncated_input() {
+        assert!(decode_framed(&[0, 0]).is_err());
+        assert!(decode_framed(&[0, 0, 0, 10, b'G']).is_err());
+    }
+}
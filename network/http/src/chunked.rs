@@ -0,0 +1,298 @@
+use crate::{HttpParser, ParseError};
+use http::{header, HeaderMap, HeaderName, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    pub fn parse_chunked(&self) -> Result<Vec<u8>, ParseError> {
+        self.parse_chunked_sized(DEFAULT_CHUNK_SIZE)
+    }
+    pub fn parse_chunked_sized(&self, chunk_size: usize) -> Result<Vec<u8>, ParseError> {
+        if !is_chunked(self.headers()) {
+            return self.parse_bytes();
+        }
+        self.chunked_with_trailers(self.headers(), chunk_size, &HeaderMap::new())
+    }
+    /// Writes `trailers` after the final zero-size chunk. Every trailer must be announced in the
+    /// message's `Trailer` header, and the message must use chunked transfer-encoding.
+    pub fn parse_chunked_with_trailers(&self, trailers: &HeaderMap) -> Result<Vec<u8>, ParseError> {
+        if !is_chunked(self.headers()) {
+            return Err(ParseError::NotChunked);
+        }
+        let declared = declared_trailers(self.headers());
+        if let Some(name) = trailers.keys().find(|name| !declared.contains(name)) {
+            return Err(ParseError::UndeclaredTrailer(name.to_string()));
+        }
+        self.chunked_with_trailers(self.headers(), DEFAULT_CHUNK_SIZE, trailers)
+    }
+    /// Serializes with a chunked body followed by gRPC status trailers: `grpc-status`, and
+    /// `grpc-message` percent-encoded (spaces included) when given. The head is switched to
+    /// chunked transfer-encoding and announces the trailers.
+    pub fn parse_with_grpc_trailers(&self, status: u32, message: Option<&str>) -> Result<Vec<u8>, ParseError> {
+        let mut trailers = HeaderMap::new();
+        trailers.insert(GRPC_STATUS, HeaderValue::from(status));
+        if let Some(message) = message {
+            let encoded = HeaderValue::from_str(&grpc_percent_encode(message))
+                .map_err(|_| ParseError::InvalidHeaderValue(GRPC_MESSAGE.to_string()))?;
+            trailers.insert(GRPC_MESSAGE, encoded);
+        }
+        let mut headers = self.headers().clone();
+        headers.remove(header::CONTENT_LENGTH);
+        if !is_chunked(&headers) {
+            headers.append(header::TRANSFER_ENCODING, HeaderValue::from_static("chunked"));
+        }
+        let announced = if message.is_some() { "grpc-status, grpc-message" } else { "grpc-status" };
+        headers.insert(header::TRAILER, HeaderValue::from_static(announced));
+        self.chunked_with_trailers(&headers, DEFAULT_CHUNK_SIZE, &trailers)
+    }
+    fn chunked_with_trailers(&self, headers: &HeaderMap, chunk_size: usize, trailers: &HeaderMap) -> Result<Vec<u8>, ParseError> {
+        let eol = self.line_ending.as_str();
+        let mut bytes = self.head_bytes_with(headers)?;
+        write_chunks(&mut bytes, self.body_bytes(), chunk_size, eol);
+        bytes.extend_from_slice(format!("0{eol}").as_bytes());
+        bytes.extend_from_slice(&self.parse_header_bytes_with(trailers));
+        bytes.extend_from_slice(eol.as_bytes());
+        Ok(bytes)
+    }
+}
+const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+
+/// Percent-encodes every byte outside printable ASCII, plus `%` and space.
+fn grpc_percent_encode(message: &str) -> String {
+    let mut encoded = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        match byte {
+            b'!'..=b'~' if byte != b'%' => encoded.push(char::from(byte)),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+fn declared_trailers(headers: &HeaderMap) -> Vec<HeaderName> {
+    headers.get_all(header::TRAILER)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+        .collect()
+}
+pub(crate) fn is_chunked(headers: &HeaderMap) -> bool {
+    headers.get_all(header::TRANSFER_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .last()
+        .is_some_and(|coding| coding.trim().eq_ignore_ascii_case("chunked"))
+}
+/// Decodes a chunked body back into its payload. Chunk extensions are ignored, and so are
+/// any trailer fields after the final zero-size chunk.
+pub(crate) fn decode_chunked(body: &[u8]) -> Result<Vec<u8>, ParseError> {
+    decode_chunked_prefix(body)?
+        .map(|(payload, _)| payload)
+        .ok_or_else(|| ParseError::InvalidMessage(String::from("malformed chunked body: truncated")))
+}
+/// Like `decode_chunked`, for a body that may still be arriving: `None` until the final chunk
+/// and trailers are complete, then the payload and the number of bytes the encoding took.
+pub(crate) fn decode_chunked_prefix(body: &[u8]) -> Result<Option<(Vec<u8>, usize)>, ParseError> {
+    let malformed = |what: &str| ParseError::InvalidMessage(format!("malformed chunked body: {what}"));
+    let mut rest = body;
+    let mut payload = Vec::new();
+    loop {
+        let Some((line, after)) = split_line(rest) else {
+            return Ok(None);
+        };
+        let size = core::str::from_utf8(line)
+            .ok()
+            .map(|line| line.split(';').next().unwrap_or_default().trim())
+            .and_then(|size| usize::from_str_radix(size, 16).ok())
+            .ok_or_else(|| malformed("invalid chunk size"))?;
+        rest = after;
+        if size == 0 {
+            break;
+        }
+        let chunk_end = size.checked_add(2).ok_or_else(|| malformed("chunk size too large"))?;
+        let Some(chunk) = rest.get(..chunk_end) else {
+            return Ok(None);
+        };
+        let data = chunk.strip_suffix(b"\r\n").ok_or_else(|| malformed("missing CRLF after chunk"))?;
+        payload.extend_from_slice(data);
+        rest = &rest[chunk_end..];
+    }
+    loop {
+        let Some((line, after)) = split_line(rest) else {
+            return Ok(None);
+        };
+        rest = after;
+        if line.is_empty() {
+            return Ok(Some((payload, body.len() - rest.len())));
+        }
+    }
+}
+fn split_line(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let index = bytes.windows(2).position(|window| window == b"\r\n")?;
+    Some((&bytes[..index], &bytes[index + 2..]))
+}
+/// Replaces `Transfer-Encoding` with `Content-Length` after decoding. Any codings besides
+/// `chunked` are still applied to the payload, so they move to the end of `Content-Encoding`.
+pub(crate) fn unchunk_headers(headers: &mut HeaderMap, len: usize) {
+    let codings = headers.get_all(header::TRANSFER_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|coding| !coding.is_empty() && !coding.eq_ignore_ascii_case("chunked"))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    headers.remove(header::TRANSFER_ENCODING);
+    if !codings.is_empty() {
+        let mut encodings = headers.get_all(header::CONTENT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        encodings.extend(codings);
+        if let Ok(value) = HeaderValue::from_str(&encodings.join(", ")) {
+            headers.insert(header::CONTENT_ENCODING, value);
+        }
+    }
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+}
+fn write_chunks(bytes: &mut Vec<u8>, body: &[u8], chunk_size: usize, eol: &str) {
+    for chunk in body.chunks(chunk_size.max(1)) {
+        bytes.extend_from_slice(format!("{:x}{eol}", chunk.len()).as_bytes());
+        bytes.extend_from_slice(chunk);
+        bytes.extend_from_slice(eol.as_bytes());
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{decode_chunked, decode_chunked_prefix, unchunk_headers};
+    use crate::{HttpParser, ParseError};
+    use http::{header, HeaderMap, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_chunked_sized_works() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .body("hello, chunked world")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_chunked_sized(8).unwrap();
+        let head = b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\n\r\n";
+        assert_eq!(&bytes[..head.len()], head);
+        assert_eq!(
+            &bytes[head.len()..],
+            b"8\r\nhello, c\r\n8\r\nhunked w\r\n4\r\norld\r\n0\r\n\r\n"
+        );
+        assert_eq!(decode_chunked(&bytes[head.len()..]).unwrap(), b"hello, chunked world");
+    }
+    #[test]
+    fn parse_chunked_empty_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::TRANSFER_ENCODING, "gzip, chunked")
+            .body("")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_chunked().unwrap();
+        assert!(bytes.ends_with(b"\r\n\r\n0\r\n\r\n"));
+    }
+    #[test]
+    fn parse_chunked_without_header_is_verbatim() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse_chunked(), parser.parse_bytes());
+    }
+    #[test]
+    fn parse_chunked_with_trailers_writes_declared_trailers() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .header(header::TRAILER, "Expires, X-Checksum")
+            .body("hello")
+            .unwrap();
+        let mut trailers = HeaderMap::new();
+        trailers.insert(header::EXPIRES, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        trailers.insert("x-checksum", "abc123".parse().unwrap());
+        let bytes = HttpParser::from_response(&response).parse_chunked_with_trailers(&trailers).unwrap();
+        let head = b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\ntrailer:Expires, X-Checksum\r\n\r\n";
+        assert_eq!(&bytes[..head.len()], head);
+        assert_eq!(
+            &bytes[head.len()..],
+            b"5\r\nhello\r\n0\r\nexpires:Wed, 21 Oct 2015 07:28:00 GMT\r\nx-checksum:abc123\r\n\r\n"
+        );
+        let (body, used) = decode_chunked_prefix(&bytes[head.len()..]).unwrap().unwrap();
+        assert_eq!(body, b"hello");
+        assert_eq!(used, bytes.len() - head.len());
+    }
+    #[test]
+    fn parse_chunked_with_trailers_rejects_undeclared_trailers() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .header(header::TRAILER, "Expires")
+            .body("hello")
+            .unwrap();
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc123".parse().unwrap());
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(
+            parser.parse_chunked_with_trailers(&trailers).unwrap_err(),
+            ParseError::UndeclaredTrailer(String::from("x-checksum"))
+        );
+
+        let response = Response::builder().body("hello").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_chunked_with_trailers(&HeaderMap::new()).unwrap_err(),
+            ParseError::NotChunked
+        );
+    }
+    #[test]
+    fn parse_with_grpc_trailers_follows_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_TYPE, "application/grpc")
+            .header(header::CONTENT_LENGTH, "5")
+            .body("hello")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(
+            parser.parse_with_grpc_trailers(0, None).unwrap(),
+            b"HTTP/1.1 200 OK\r\ncontent-type:application/grpc\r\ntransfer-encoding:chunked\r\n\
+              trailer:grpc-status\r\n\r\n5\r\nhello\r\n0\r\ngrpc-status:0\r\n\r\n"
+        );
+
+        let bytes = parser.parse_with_grpc_trailers(13, Some("not found: café 100%")).unwrap();
+        let trailers = b"0\r\ngrpc-status:13\r\ngrpc-message:not%20found:%20caf%C3%A9%20100%25\r\n\r\n";
+        assert!(bytes.ends_with(trailers));
+        assert!(String::from_utf8(bytes).unwrap().contains("trailer:grpc-status, grpc-message\r\n\r\n5\r\nhello"));
+    }
+    #[test]
+    fn unchunk_headers_moves_other_codings_to_content_encoding() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::TRANSFER_ENCODING, "gzip, chunked".parse().unwrap());
+        headers.insert(header::CONTENT_ENCODING, "br".parse().unwrap());
+        unchunk_headers(&mut headers, 12);
+        assert!(!headers.contains_key(header::TRANSFER_ENCODING));
+        assert_eq!(headers[header::CONTENT_ENCODING], "br, gzip");
+        assert_eq!(headers[header::CONTENT_LENGTH], "12");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::TRANSFER_ENCODING, "chunked".parse().unwrap());
+        unchunk_headers(&mut headers, 0);
+        assert!(!headers.contains_key(header::CONTENT_ENCODING));
+        assert_eq!(headers[header::CONTENT_LENGTH], "0");
+    }
+}
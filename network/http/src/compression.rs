@@ -0,0 +1,277 @@
+use crate::{chunked, HttpParser, ParseError};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use http::{header, HeaderMap, HeaderValue};
+use std::io::{Read, Write};
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// Applies the codings listed in `Content-Encoding` (`gzip` and `deflate`) to the body, in order,
+    /// and sets `Content-Length` to the compressed size unless the message is chunked.
+    /// Without a supported `Content-Encoding`, or for a 1xx or `304` response, this is the same
+    /// as `parse_bytes()`.
+    pub fn parse_compressed(&self) -> Result<Vec<u8>, ParseError> {
+        let Some(codings) = content_codings(self.headers()).filter(|_| self.sends_body()) else {
+            return self.parse_bytes();
+        };
+        let mut body = self.body_bytes().to_vec();
+        for coding in codings {
+            body = encode(coding, &body).map_err(|error| ParseError::Compression(error.to_string()))?;
+        }
+        let mut headers = self.headers().clone();
+        if !chunked::is_chunked(&headers) {
+            headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        }
+        let mut bytes = self.head_bytes_with(&headers)?;
+        bytes.extend_from_slice(&body);
+        Ok(bytes)
+    }
+}
+/// The most `TryFrom<&[u8]>` will decompress a body to, as a guard against decompression bombs.
+pub(crate) const MAX_DECODED_LEN: usize = 64 * 1024 * 1024;
+/// Undoes the codings in `Content-Encoding`, last applied first, removing each from the header.
+/// Decoding stops at a coding it does not know, which stays listed along with those before it.
+/// A `Content-Length` is updated to the decoded size. Fails once any layer decodes past `max` bytes.
+pub(crate) fn decode_content(headers: &mut HeaderMap, body: &mut Vec<u8>, max: usize) -> Result<(), ParseError> {
+    let Some(values) = headers.get_all(header::CONTENT_ENCODING)
+        .iter()
+        .map(|value| value.to_str().ok())
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(());
+    };
+    let mut codings = values.iter()
+        .flat_map(|value| value.split(','))
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty())
+        .collect::<Vec<_>>();
+    let declared = codings.len();
+    while let Some(coding) = codings.last() {
+        let mut decoded = Vec::new();
+        let result = match coding.as_str() {
+            "gzip" | "x-gzip" => read_limited(GzDecoder::new(body.as_slice()), max, &mut decoded),
+            "deflate" => read_limited(ZlibDecoder::new(body.as_slice()), max, &mut decoded),
+            "br" => read_limited(brotli_decompressor::Decompressor::new(body.as_slice(), 4096), max, &mut decoded),
+            "identity" => Ok(0),
+            _ => break,
+        };
+        result.map_err(|error| ParseError::Compression(error.to_string()))?;
+        if decoded.len() > max {
+            return Err(ParseError::DecodedBodyTooLarge { max });
+        }
+        if coding != "identity" {
+            *body = decoded;
+        }
+        codings.pop();
+    }
+    if codings.len() == declared {
+        return Ok(());
+    }
+    if codings.is_empty() {
+        headers.remove(header::CONTENT_ENCODING);
+    } else {
+        let remaining = HeaderValue::from_str(&codings.join(", "))
+            .map_err(|_| ParseError::InvalidHeaderValue(header::CONTENT_ENCODING.to_string()))?;
+        headers.insert(header::CONTENT_ENCODING, remaining);
+    }
+    if headers.contains_key(header::CONTENT_LENGTH) {
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+    }
+    Ok(())
+}
+/// Reads at most one byte past `max`, which is enough to tell the output is too large.
+fn read_limited(reader: impl Read, max: usize, decoded: &mut Vec<u8>) -> std::io::Result<usize> {
+    reader.take(u64::try_from(max).unwrap_or(u64::MAX).saturating_add(1)).read_to_end(decoded)
+}
+#[derive(Clone, Copy)]
+enum Coding {
+    Gzip,
+    Deflate,
+    Identity,
+}
+/// The codings from `Content-Encoding` in application order, or `None` if the header is
+/// absent or names a coding this crate cannot produce.
+fn content_codings(headers: &HeaderMap) -> Option<Vec<Coding>> {
+    let codings = headers.get_all(header::CONTENT_ENCODING)
+        .iter()
+        .map(|value| value.to_str().ok())
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(|coding| match coding.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Coding::Gzip),
+            "deflate" => Some(Coding::Deflate),
+            "identity" => Some(Coding::Identity),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if codings.is_empty() {
+        return None;
+    }
+    Some(codings)
+}
+fn encode(coding: Coding, body: &[u8]) -> std::io::Result<Vec<u8>> {
+    match coding {
+        Coding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        Coding::Deflate => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        Coding::Identity => Ok(body.to_vec()),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::decode_content;
+    use crate::{HttpMessage, HttpParser, ParseError};
+    use flate2::read::{GzDecoder, ZlibDecoder};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use http::{header, Response, StatusCode, Version};
+    use std::io::{Read, Write};
+
+    fn split_body(bytes: &[u8]) -> (&[u8], &[u8]) {
+        let index = bytes.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+        bytes.split_at(index)
+    }
+
+    #[test]
+    fn parse_compressed_gzip_round_trip() {
+        let text = "hello, hello, hello, compressed world";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "gzip")
+            .header(header::CONTENT_LENGTH, text.len())
+            .body(text)
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_compressed().unwrap();
+        let (head, body) = split_body(&bytes);
+        let head = String::from_utf8(head.to_vec()).unwrap();
+        assert!(head.contains(&format!("content-length:{}\r\n", body.len())));
+
+        let mut decoded = String::new();
+        GzDecoder::new(body).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, text);
+    }
+    #[test]
+    fn parse_compressed_deflate_round_trip() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "deflate")
+            .body("deflated body")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_compressed().unwrap();
+        let mut decoded = String::new();
+        ZlibDecoder::new(split_body(&bytes).1).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, "deflated body");
+    }
+    #[test]
+    fn parse_compressed_without_encoding_is_parse_bytes() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("plain")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse_compressed(), parser.parse_bytes());
+
+        let not_modified = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "gzip")
+            .header(header::CONTENT_LENGTH, "1024")
+            .body("accidental")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&not_modified).parse_compressed().unwrap(),
+            b"HTTP/1.1 304 Not Modified\r\ncontent-encoding:gzip\r\ncontent-length:1024\r\n\r\n"
+        );
+    }
+    #[test]
+    fn try_from_decodes_content_encoding() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body("captured body")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_compressed().unwrap();
+        let HttpMessage::Response(decoded) = HttpMessage::try_from(bytes.as_slice()).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(decoded.body(), b"captured body");
+        assert!(!decoded.headers().contains_key(header::CONTENT_ENCODING));
+        assert_eq!(decoded.headers()[header::CONTENT_LENGTH], "13");
+
+        let raw = b"HTTP/1.1 200 OK\r\ncontent-encoding: zstd\r\ncontent-length: 4\r\n\r\nabcd";
+        let HttpMessage::Response(untouched) = HttpMessage::try_from(&raw[..]).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(untouched.body(), b"abcd");
+        assert_eq!(untouched.headers()[header::CONTENT_ENCODING], "zstd");
+    }
+    #[test]
+    fn try_from_decodes_stacked_encodings() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "deflate, gzip")
+            .body("twice encoded")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_compressed().unwrap();
+        let HttpMessage::Response(decoded) = HttpMessage::try_from(bytes.as_slice()).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(decoded.body(), b"twice encoded");
+        assert!(!decoded.headers().contains_key(header::CONTENT_ENCODING));
+    }
+    #[test]
+    fn try_from_decodes_brotli() {
+        // An uncompressed brotli meta-block holding the text, then an empty last meta-block.
+        const BROTLI: &[u8] = b"\xa0\x00\x10brotli body\x03";
+        let mut raw = b"HTTP/1.1 200 OK\r\ncontent-encoding:br\r\n\r\n".to_vec();
+        raw.extend_from_slice(BROTLI);
+        let HttpMessage::Response(decoded) = HttpMessage::try_from(raw.as_slice()).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(decoded.body(), b"brotli body");
+        assert!(!decoded.headers().contains_key(header::CONTENT_ENCODING));
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(BROTLI).unwrap();
+        let mut raw = b"HTTP/1.1 200 OK\r\ncontent-encoding:br, gzip\r\n\r\n".to_vec();
+        raw.extend_from_slice(&encoder.finish().unwrap());
+        let HttpMessage::Response(decoded) = HttpMessage::try_from(raw.as_slice()).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(decoded.body(), b"brotli body");
+        assert!(!decoded.headers().contains_key(header::CONTENT_ENCODING));
+    }
+    #[test]
+    fn decode_content_stops_at_max_len() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body("a".repeat(4096))
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_compressed().unwrap();
+        let mut headers = response.headers().clone();
+        let mut body = split_body(&bytes).1.to_vec();
+        assert_eq!(
+            decode_content(&mut headers, &mut body, 1024).unwrap_err(),
+            ParseError::DecodedBodyTooLarge { max: 1024 }
+        );
+        assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+
+        decode_content(&mut headers, &mut body, 4096).unwrap();
+        assert_eq!(body.len(), 4096);
+    }
+}
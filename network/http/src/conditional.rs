@@ -0,0 +1,64 @@
+use crate::ParseError;
+use http::{header, HeaderValue, Method, Request, Uri, Version};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A revalidation GET carrying `If-None-Match` for a stored ETag. The tag may be given bare
+/// or already quoted; a `W/` weak prefix is kept. `Host` comes from the URI's authority, if any.
+pub fn conditional_get(uri: &str, etag: &str) -> Result<Request<String>, ParseError> {
+    let uri = uri.parse::<Uri>().map_err(|_| ParseError::InvalidUri(uri.to_string()))?;
+    let mut builder = Request::builder()
+        .method(Method::GET)
+        .version(Version::HTTP_11)
+        .header(header::IF_NONE_MATCH, quote_etag(etag)?);
+    if let Some(authority) = uri.authority() {
+        builder = builder.header(header::HOST, authority.as_str());
+    }
+    builder.uri(uri)
+        .body(String::new())
+        .map_err(|error| ParseError::InvalidMessage(error.to_string()))
+}
+fn quote_etag(etag: &str) -> Result<HeaderValue, ParseError> {
+    let (weak, tag) = match etag.strip_prefix("W/") {
+        Some(tag) => ("W/", tag),
+        None => ("", etag),
+    };
+    let opaque = tag.strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .unwrap_or(tag);
+    // etagc = %x21 / %x23-7E / obs-text
+    if !opaque.bytes().all(|b| b == 0x21 || (0x23..=0x7e).contains(&b) || b >= 0x80) {
+        return Err(ParseError::InvalidHeaderValue(header::IF_NONE_MATCH.to_string()));
+    }
+    HeaderValue::from_str(&format!("{weak}\"{opaque}\""))
+        .map_err(|_| ParseError::InvalidHeaderValue(header::IF_NONE_MATCH.to_string()))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{conditional_get, HttpParser, ParseError};
+
+    #[test]
+    fn conditional_get_quotes_etags() {
+        let request = conditional_get("http://example.com/logo.png", "abc123").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse().unwrap(),
+            "GET http://example.com/logo.png HTTP/1.1\r\nif-none-match:\"abc123\"\r\nhost:example.com\r\n\r\n"
+        );
+
+        let request = conditional_get("/logo.png", "W/\"abc123\"").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse().unwrap(),
+            "GET /logo.png HTTP/1.1\r\nif-none-match:W/\"abc123\"\r\n\r\n"
+        );
+        assert_eq!(conditional_get("/", "\"abc\"").unwrap().headers()["if-none-match"], "\"abc\"");
+        assert_eq!(conditional_get("/", "W/abc").unwrap().headers()["if-none-match"], "W/\"abc\"");
+    }
+    #[test]
+    fn conditional_get_rejects_invalid_etags() {
+        assert_eq!(
+            conditional_get("/", "a\"b").unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("if-none-match"))
+        );
+        assert!(conditional_get("/", "has space").is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{encode_ext_value, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disposition {
+    Inline,
+    Attachment,
+}
+impl Disposition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Disposition::Inline => "inline",
+            Disposition::Attachment => "attachment",
+        }
+    }
+}
+/// A `Content-Disposition` value (RFC 6266). An ASCII filename is quoted as `filename="..."`;
+/// a non-ASCII one gets an ASCII fallback with `_` for each other char, then `filename*=UTF-8''...`.
+/// Filenames with control characters are rejected.
+pub fn content_disposition(disposition: Disposition, filename: Option<&str>) -> Result<HeaderValue, ParseError> {
+    let invalid = || ParseError::InvalidHeaderValue(header::CONTENT_DISPOSITION.to_string());
+    let mut value = String::from(disposition.as_str());
+    if let Some(filename) = filename {
+        if filename.chars().any(char::is_control) {
+            return Err(invalid());
+        }
+        let fallback = filename.chars()
+            .map(|c| if c.is_ascii() { c } else { '_' })
+            .collect::<String>()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        value.push_str(&format!("; filename=\"{fallback}\""));
+        if !filename.is_ascii() {
+            value.push_str(&format!("; filename*={}", encode_ext_value("UTF-8", filename)?));
+        }
+    }
+    HeaderValue::from_str(&value).map_err(|_| invalid())
+}
+#[cfg(test)]
+mod tests {
+    use crate::{content_disposition, decode_ext_value, Disposition, HttpParser, ParseError};
+    use http::{header, Response, StatusCode};
+
+    #[test]
+    fn content_disposition_quotes_ascii_filenames() {
+        let value = content_disposition(Disposition::Attachment, Some("annual report.pdf")).unwrap();
+        assert_eq!(value, "attachment; filename=\"annual report.pdf\"");
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_DISPOSITION, value)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 200 OK\r\ncontent-disposition:attachment; filename=\"annual report.pdf\"\r\n\r\n"
+        );
+        assert_eq!(content_disposition(Disposition::Inline, None).unwrap(), "inline");
+        assert_eq!(
+            content_disposition(Disposition::Inline, Some("say \"hi\".txt")).unwrap(),
+            "inline; filename=\"say \\\"hi\\\".txt\""
+        );
+    }
+    #[test]
+    fn content_disposition_adds_extended_filename() {
+        let value = content_disposition(Disposition::Attachment, Some("résumé €.pdf")).unwrap();
+        assert_eq!(
+            value,
+            "attachment; filename=\"r_sum_ _.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%20%E2%82%AC.pdf"
+        );
+        let extended = value.to_str().unwrap().split_once("filename*=").unwrap().1;
+        assert_eq!(decode_ext_value(extended).unwrap(), "résumé €.pdf");
+        assert_eq!(
+            content_disposition(Disposition::Attachment, Some("a\nb")).unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("content-disposition"))
+        );
+    }
+}
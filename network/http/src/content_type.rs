@@ -0,0 +1,198 @@
+use crate::{headers, HttpParser, ParseError};
+use http::{header, HeaderValue};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+/// Builds a `Content-Type` value, quoting parameter values that are not tokens.
+#[derive(Clone, Debug)]
+pub struct ContentType {
+    mime: String,
+    params: Vec<(String, String)>,
+}
+impl ContentType {
+    /// Starts from a `type/subtype` such as `text/html`.
+    pub fn new(mime: &str) -> Self {
+        ContentType { mime: mime.to_string(), params: Vec::new() }
+    }
+    pub fn charset(self, charset: &str) -> Self {
+        self.param("charset", charset)
+    }
+    pub fn boundary(self, boundary: &str) -> Self {
+        self.param("boundary", boundary)
+    }
+    pub fn param(mut self, name: &str, value: &str) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+    pub fn build(&self) -> Result<HeaderValue, ParseError> {
+        let invalid = || ParseError::InvalidHeaderValue(header::CONTENT_TYPE.to_string());
+        let valid_mime = self.mime
+            .split_once('/')
+            .is_some_and(|(kind, subtype)| headers::is_token(kind) && headers::is_token(subtype));
+        if !valid_mime {
+            return Err(invalid());
+        }
+        let mut value = self.mime.clone();
+        for (name, param) in &self.params {
+            if !headers::is_token(name) {
+                return Err(invalid());
+            }
+            if headers::is_token(param) {
+                value.push_str(&format!("; {name}={param}"));
+            } else {
+                let escaped = param.replace('\\', "\\\\").replace('"', "\\\"");
+                value.push_str(&format!("; {name}=\"{escaped}\""));
+            }
+        }
+        HeaderValue::from_str(&value).map_err(|_| invalid())
+    }
+}
+/// A parsed `Content-Type`. Type, subtype and parameter names are lowercased; quoted
+/// parameter values are unquoted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MediaType {
+    pub type_: String,
+    pub subtype: String,
+    pub params: BTreeMap<String, String>,
+}
+impl MediaType {
+    pub fn charset(&self) -> Option<&str> {
+        self.params.get("charset").map(String::as_str)
+    }
+    pub fn boundary(&self) -> Option<&str> {
+        self.params.get("boundary").map(String::as_str)
+    }
+}
+/// Writes `type/subtype; name=value`, quoting values that are not tokens.
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.type_, self.subtype)?;
+        for (name, value) in &self.params {
+            if headers::is_token(value) {
+                write!(f, "; {name}={value}")?;
+            } else {
+                write!(f, "; {name}=\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))?;
+            }
+        }
+        Ok(())
+    }
+}
+impl<T> HttpParser<'_, T> {
+    /// The message's `Content-Type`, or `None` if it is missing or malformed.
+    pub fn content_type(&self) -> Option<MediaType> {
+        let value = self.headers().get(header::CONTENT_TYPE)?.to_str().ok()?;
+        parse_media_type(value)
+    }
+}
+fn parse_media_type(value: &str) -> Option<MediaType> {
+    let (mime, mut rest) = value.split_once(';').unwrap_or((value, ""));
+    let (type_, subtype) = mime.trim().split_once('/')?;
+    if !headers::is_token(type_) || !headers::is_token(subtype) {
+        return None;
+    }
+    let mut params = BTreeMap::new();
+    while !rest.trim().is_empty() {
+        let (name, after) = rest.split_once('=')?;
+        let name = name.trim();
+        if !headers::is_token(name) {
+            return None;
+        }
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => unquote(quoted)?,
+            None => {
+                let (value, remaining) = after.split_once(';').unwrap_or((after, ""));
+                (value.trim_end().to_string(), remaining)
+            }
+        };
+        params.insert(name.to_ascii_lowercase(), value);
+        rest = remaining;
+    }
+    Some(MediaType {
+        type_: type_.to_ascii_lowercase(),
+        subtype: subtype.to_ascii_lowercase(),
+        params,
+    })
+}
+/// Reads a quoted-string body up to its closing quote, then skips to after the next `;`.
+fn unquote(quoted: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '"' => {
+                let after = quoted[index + 1..].trim_start();
+                return match after.strip_prefix(';') {
+                    Some(remaining) => Some((value, remaining)),
+                    None => after.is_empty().then_some((value, "")),
+                };
+            }
+            c => value.push(c),
+        }
+    }
+    None
+}
+#[cfg(test)]
+mod tests {
+    use crate::{ContentType, HttpParser, ParseError};
+    use http::{header, Response, StatusCode};
+
+    #[test]
+    fn content_type_with_charset() {
+        let value = ContentType::new("text/html").charset("utf-8").build().unwrap();
+        assert_eq!(value, "text/html; charset=utf-8");
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, value)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 200 OK\r\ncontent-type:text/html; charset=utf-8\r\n\r\n"
+        );
+    }
+    #[test]
+    fn content_type_quotes_special_values() {
+        let value = ContentType::new("multipart/form-data").boundary("----=_Part 1").build().unwrap();
+        assert_eq!(value, "multipart/form-data; boundary=\"----=_Part 1\"");
+        let value = ContentType::new("text/plain").param("title", "say \"hi\"").build().unwrap();
+        assert_eq!(value, "text/plain; title=\"say \\\"hi\\\"\"");
+    }
+    #[test]
+    fn content_type_rejects_invalid_parts() {
+        let invalid = ParseError::InvalidHeaderValue(String::from("content-type"));
+        assert_eq!(ContentType::new("text").build().unwrap_err(), invalid);
+        assert_eq!(ContentType::new("text/html").param("bad name", "x").build().unwrap_err(), invalid);
+        assert_eq!(ContentType::new("text/html").charset("utf-8\r\n").build().unwrap_err(), invalid);
+    }
+    #[test]
+    fn content_type_parses_media_type() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "Text/HTML; Charset=utf-8")
+            .body("")
+            .unwrap();
+        let media_type = HttpParser::from_response(&response).content_type().unwrap();
+        assert_eq!(media_type.type_, "text");
+        assert_eq!(media_type.subtype, "html");
+        assert_eq!(media_type.charset(), Some("utf-8"));
+        assert_eq!(media_type.params.len(), 1);
+
+        let response = Response::builder()
+            .header(header::CONTENT_TYPE, r#"multipart/form-data; boundary="a; \"b\""; x=1"#)
+            .body("")
+            .unwrap();
+        let media_type = HttpParser::from_response(&response).content_type().unwrap();
+        assert_eq!(media_type.boundary(), Some(r#"a; "b""#));
+        assert_eq!(media_type.params["x"], "1");
+
+        let missing = Response::builder().body("").unwrap();
+        assert_eq!(HttpParser::from_response(&missing).content_type(), None);
+        let malformed = Response::builder().header(header::CONTENT_TYPE, "text; charset").body("").unwrap();
+        assert_eq!(HttpParser::from_response(&malformed).content_type(), None);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{headers, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+/// Builds a `Set-Cookie` value (RFC 6265). Attributes are written in a fixed order:
+/// `Path`, `Domain`, `Secure`, `HttpOnly`, `SameSite`, `Max-Age`.
+#[derive(Clone, Debug)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+    max_age: Option<u64>,
+}
+impl Cookie {
+    pub fn new(name: &str, value: &str) -> Self {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            path: None,
+            domain: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+            max_age: None,
+        }
+    }
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+    /// The name must be a token and the value plain cookie-octets (no spaces, quotes, commas,
+    /// semicolons or backslashes); attribute values can't contain `;` or control characters.
+    pub fn build(&self) -> Result<HeaderValue, ParseError> {
+        let invalid = || ParseError::InvalidHeaderValue(header::SET_COOKIE.to_string());
+        let cookie_octet = |b: u8| matches!(b, 0x21 | 0x23..=0x2b | 0x2d..=0x3a | 0x3c..=0x5b | 0x5d..=0x7e);
+        if !headers::is_token(&self.name) || !self.value.bytes().all(cookie_octet) {
+            return Err(invalid());
+        }
+        let mut cookie = format!("{}={}", self.name, self.value);
+        for (attribute, value) in [("Path", &self.path), ("Domain", &self.domain)] {
+            if let Some(value) = value {
+                if value.bytes().any(|b| b == b';' || b.is_ascii_control()) {
+                    return Err(invalid());
+                }
+                cookie.push_str(&format!("; {attribute}={value}"));
+            }
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        if self.http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        if let Some(same_site) = self.same_site {
+            cookie.push_str(&format!("; SameSite={}", same_site.as_str()));
+        }
+        if let Some(max_age) = self.max_age {
+            cookie.push_str(&format!("; Max-Age={max_age}"));
+        }
+        HeaderValue::from_str(&cookie).map_err(|_| invalid())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{Cookie, HttpParser, ParseError, SameSite};
+    use http::{header, Response, StatusCode};
+
+    #[test]
+    fn cookie_renders_attributes_in_order() {
+        let cookie = Cookie::new("sid", "abc")
+            .max_age(3600)
+            .same_site(SameSite::Strict)
+            .http_only(true)
+            .secure(true)
+            .path("/")
+            .build()
+            .unwrap();
+        assert_eq!(cookie, "sid=abc; Path=/; Secure; HttpOnly; SameSite=Strict; Max-Age=3600");
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::SET_COOKIE, cookie)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 200 OK\r\nset-cookie:sid=abc; Path=/; Secure; HttpOnly; SameSite=Strict; Max-Age=3600\r\n\r\n"
+        );
+
+        let cookie = Cookie::new("theme", "dark").domain("example.com").same_site(SameSite::Lax).build().unwrap();
+        assert_eq!(cookie, "theme=dark; Domain=example.com; SameSite=Lax");
+    }
+    #[test]
+    fn cookie_rejects_invalid_name_and_value() {
+        let invalid = ParseError::InvalidHeaderValue(String::from("set-cookie"));
+        assert_eq!(Cookie::new("bad name", "x").build().unwrap_err(), invalid);
+        assert_eq!(Cookie::new("sid", "a;b").build().unwrap_err(), invalid);
+        assert_eq!(Cookie::new("sid", "a b").build().unwrap_err(), invalid);
+        assert_eq!(Cookie::new("sid", "x").path("/; Secure").build().unwrap_err(), invalid);
+    }
+}
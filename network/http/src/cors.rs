@@ -0,0 +1,65 @@
+use crate::ParseError;
+use http::{header, HeaderName, HeaderValue, Method, Response, StatusCode};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A `204` answer to a CORS preflight allowing `origin` (or `*`) to use `methods` and send
+/// `headers`; empty lists leave their header out. A specific origin also gets `Vary: Origin` so caches keep answers apart.
+pub fn cors_preflight_response(
+    origin: &str,
+    methods: &[Method],
+    headers: &[HeaderName],
+) -> Result<Response<String>, ParseError> {
+    let allow_origin = HeaderValue::from_str(origin)
+        .map_err(|_| ParseError::InvalidHeaderValue(header::ACCESS_CONTROL_ALLOW_ORIGIN.to_string()))?;
+    let allow_methods = methods.iter().map(Method::as_str).collect::<Vec<_>>().join(", ");
+    let allow_methods = HeaderValue::from_str(&allow_methods)
+        .map_err(|_| ParseError::InvalidHeaderValue(header::ACCESS_CONTROL_ALLOW_METHODS.to_string()))?;
+    let allow_headers = headers.iter().map(HeaderName::as_str).collect::<Vec<_>>().join(", ");
+    let allow_headers = HeaderValue::from_str(&allow_headers)
+        .map_err(|_| ParseError::InvalidHeaderValue(header::ACCESS_CONTROL_ALLOW_HEADERS.to_string()))?;
+
+    let mut response = Response::new(String::new());
+    *response.status_mut() = StatusCode::NO_CONTENT;
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+    if !methods.is_empty() {
+        response_headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, allow_methods);
+    }
+    if !headers.is_empty() {
+        response_headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, allow_headers);
+    }
+    if origin != "*" {
+        response_headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+    Ok(response)
+}
+#[cfg(test)]
+mod tests {
+    use crate::{cors_preflight_response, HttpParser};
+    use http::{header, Method};
+
+    #[test]
+    fn cors_preflight_response_sets_allow_headers() {
+        let response = cors_preflight_response(
+            "https://app.example",
+            &[Method::GET, Method::POST, Method::DELETE],
+            &[header::CONTENT_TYPE, header::AUTHORIZATION],
+        )
+        .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 204 No Content\r\n\
+             access-control-allow-origin:https://app.example\r\n\
+             access-control-allow-methods:GET, POST, DELETE\r\n\
+             access-control-allow-headers:content-type, authorization\r\n\
+             vary:Origin\r\n\r\n"
+        );
+
+        let response = cors_preflight_response("*", &[Method::GET], &[]).unwrap();
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_HEADERS));
+        assert!(!response.headers().contains_key(header::VARY));
+        assert!(cors_preflight_response("bad\norigin", &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes a response with a `Date` header for the current time, unless one is already set.
+    pub fn parse_response_with_date(&self) -> Result<String, ParseError> {
+        self.parse_response_with_clock(SystemTime::now)
+    }
+    /// Like `parse_response_with_date()`, reading the time from `clock` so tests can pin it.
+    pub fn parse_response_with_clock(&self, clock: fn() -> SystemTime) -> Result<String, ParseError> {
+        let Message::Response(response) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        if response.headers().contains_key(header::DATE) {
+            return self.parse();
+        }
+        let mut headers = response.headers().clone();
+        let date = HeaderValue::from_str(&imf_fixdate(clock()))
+            .map_err(|_| ParseError::InvalidHeaderValue(header::DATE.to_string()))?;
+        headers.insert(header::DATE, date);
+        self.parse_with_headers(&headers)
+    }
+}
+/// Formats `time` as an RFC 7231 IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn imf_fixdate(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let days = seconds / 86400;
+    let (hour, minute, second) = (seconds % 86400 / 3600, seconds % 3600 / 60, seconds % 60);
+    let weekday = WEEKDAYS[((days + 4) % 7) as usize];
+    // Civil-from-days over 400-year eras, as in Howard Hinnant's date algorithms.
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    let month = MONTHS[(month - 1) as usize];
+    format!("{weekday}, {day:02} {month} {year} {hour:02}:{minute:02}:{second:02} GMT")
+}
+#[cfg(test)]
+mod tests {
+    use super::imf_fixdate;
+    use crate::{HttpParser, ParseError};
+    use http::{header, Request, Response, StatusCode, Version};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn fixed_clock() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(784111777)
+    }
+
+    #[test]
+    fn imf_fixdate_formats() {
+        assert_eq!(imf_fixdate(fixed_clock()), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(imf_fixdate(UNIX_EPOCH), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(imf_fixdate(UNIX_EPOCH + Duration::from_secs(951782400)), "Tue, 29 Feb 2000 00:00:00 GMT");
+    }
+    #[test]
+    fn parse_response_with_clock_inserts_date() {
+        let response = Response::builder().status(StatusCode::OK).version(Version::HTTP_11).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_response_with_clock(fixed_clock).unwrap(),
+            "HTTP/1.1 200 OK\r\ndate:Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n"
+        );
+        assert!(HttpParser::from_response(&response).parse_response_with_date().unwrap().contains("date:"));
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::DATE, "Mon, 01 Jan 2024 00:00:00 GMT")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_response_with_clock(fixed_clock).unwrap(),
+            "HTTP/1.1 200 OK\r\ndate:Mon, 01 Jan 2024 00:00:00 GMT\r\n\r\n"
+        );
+
+        let request = Request::builder().uri("/").body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_response_with_date().unwrap_err(),
+            ParseError::WrongVariant
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderName};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt::Display;
+
+/// Credentials that `debug_redacted(DEFAULT_REDACTED_HEADERS)` keeps out of logs.
+pub const DEFAULT_REDACTED_HEADERS: &[HeaderName] = &[
+    header::AUTHORIZATION,
+    header::COOKIE,
+    header::SET_COOKIE,
+    header::PROXY_AUTHORIZATION,
+];
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// A human-readable dump for logs, not for the wire: `name: value` headers with
+    /// aligned values, `\n` line endings, and a `[body: N bytes]` marker instead of the body.
+    pub fn debug_string(&self) -> String {
+        self.debug_redacted(&[])
+    }
+    /// Like `debug_string()`, with the values of the `redact` headers replaced by `<redacted>`.
+    pub fn debug_redacted(&self, redact: &[HeaderName]) -> String {
+        let start_line = self.parse_start_line()
+            .unwrap_or_else(|_| format!("<unsupported version {:?}>", self.version()));
+        let width = self.headers()
+            .keys()
+            .map(|name| name.as_str().len() + 1)
+            .max()
+            .unwrap_or_default();
+        let mut lines = vec![start_line];
+        for (name, value) in self.headers() {
+            let value = if redact.contains(name) {
+                "<redacted>".into()
+            } else {
+                String::from_utf8_lossy(value.as_bytes())
+            };
+            let name = format!("{name}:");
+            lines.push(format!("{name:width$} {value}"));
+        }
+        lines.push(format!("[body: {} bytes]", self.body().as_ref().len()));
+        lines.join("\n")
+    }
+    /// The body for logs: printable ASCII as is, every other byte (controls, DEL, non-ASCII)
+    /// as a `\xNN` escape, so nothing can reach the terminal as a control sequence.
+    pub fn debug_body_escaped(&self) -> String {
+        let mut escaped = String::new();
+        for &byte in self.body().as_ref() {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                escaped.push(char::from(byte));
+            } else {
+                escaped.push_str(&format!("\\x{byte:02x}"));
+            }
+        }
+        escaped
+    }
+}
+impl<T: Display> HttpParser<'_, T> {
+    /// A `curl` command line reproducing the request, for bug reports. Origin-form targets are
+    /// made absolute from the `Host` header; every argument is single-quoted for POSIX shells.
+    pub fn to_curl(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let uri = request.uri();
+        let url = match (uri.authority(), request.headers().get(header::HOST)) {
+            (None, Some(host)) => format!("http://{}{uri}", String::from_utf8_lossy(host.as_bytes())),
+            _ => uri.to_string(),
+        };
+        let mut command = format!("curl -X {}", shell_quote(request.method().as_str()));
+        for (name, value) in request.headers() {
+            let header = format!("{name}: {}", String::from_utf8_lossy(value.as_bytes()));
+            command.push_str(&format!(" -H {}", shell_quote(&header)));
+        }
+        let body = request.body().to_string();
+        if !body.is_empty() {
+            command.push_str(&format!(" --data-raw {}", shell_quote(&body)));
+        }
+        command.push_str(&format!(" {}", shell_quote(&url)));
+        Ok(command)
+    }
+}
+fn shell_quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', "'\\''"))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError, DEFAULT_REDACTED_HEADERS};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn debug_string_aligns_headers() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/submit")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .header(header::CONTENT_TYPE, "text/html")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(
+            parser.debug_string(),
+            "POST /submit HTTP/1.1\nhost:         localhost\ncontent-type: text/html\n[body: 14 bytes]"
+        );
+        assert_ne!(parser.debug_string(), parser.parse().unwrap());
+    }
+    #[test]
+    fn debug_body_escaped_escapes_controls() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(b"a\0b\nc\x1b[31m\xff ok".to_vec())
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).debug_body_escaped(),
+            "a\\x00b\\x0ac\\x1b[31m\\xff ok"
+        );
+    }
+    #[test]
+    fn debug_redacted_hides_credentials() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/account")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .header(header::AUTHORIZATION, "Bearer secret-token")
+            .header(header::COOKIE, "session=abc123")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let redacted = parser.debug_redacted(DEFAULT_REDACTED_HEADERS);
+        assert_eq!(
+            redacted,
+            "GET /account HTTP/1.1\nhost:          localhost\nauthorization: <redacted>\ncookie:        <redacted>\n[body: 0 bytes]"
+        );
+        assert!(!redacted.contains("secret-token") && !redacted.contains("abc123"));
+
+        let redacted = parser.debug_redacted(&[header::COOKIE]);
+        assert!(redacted.contains("Bearer secret-token") && !redacted.contains("abc123"));
+    }
+    #[test]
+    fn to_curl_quotes_arguments() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/search?q=it's")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "example.com")
+            .header("x-note", "don't panic")
+            .body("a 'quoted' body")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).to_curl().unwrap(),
+            "curl -X 'GET' -H 'host: example.com' -H 'x-note: don'\\''t panic' \
+             --data-raw 'a '\\''quoted'\\'' body' 'http://example.com/search?q=it'\\''s'"
+        );
+
+        let response = Response::builder().status(StatusCode::OK).body("").unwrap();
+        assert_eq!(HttpParser::from_response(&response).to_curl().unwrap_err(), ParseError::WrongVariant);
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{headers, version_from_str, HttpParser, ParseError};
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri, Version};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+impl HttpParser<'_, String> {
+    pub fn parse_request_str(raw: &str) -> Result<Request<String>, ParseError> {
+        let (head, body) = raw.split_once("\r\n\r\n")
+            .ok_or(ParseError::MissingHeadTerminator)?;
+        let request = parse_request_head(head)?;
+        Ok(request.map(|_| body.to_string()))
+    }
+    pub fn parse_response_str(raw: &str) -> Result<Response<String>, ParseError> {
+        let (head, body) = raw.split_once("\r\n\r\n")
+            .ok_or(ParseError::MissingHeadTerminator)?;
+        let response = parse_response_head(head)?;
+        Ok(response.map(|_| body.to_string()))
+    }
+    /// Like `parse_request_str`, but gives up with `HeadersTooLarge` once the head (start line
+    /// and headers) exceeds `limit` bytes, without scanning the rest of the input.
+    pub fn parse_request_str_limited(raw: &str, limit: usize) -> Result<Request<String>, ParseError> {
+        let window = &raw.as_bytes()[..raw.len().min(limit.saturating_add(4))];
+        let Some(index) = window.windows(4).position(|window| window == b"\r\n\r\n") else {
+            return Err(if raw.len() > limit { ParseError::HeadersTooLarge } else { ParseError::MissingHeadTerminator });
+        };
+        Self::parse_request_str(&raw[..index + 4])
+            .map(|request| request.map(|_| raw[index + 4..].to_string()))
+    }
+}
+/// Parses a status line and headers into a bodiless `Response`, for header-only fixtures.
+/// The trailing blank line is optional.
+pub fn response_from_head(head: &str) -> Result<Response<()>, ParseError> {
+    let head = head.strip_suffix("\r\n\r\n").or_else(|| head.strip_suffix("\r\n")).unwrap_or(head);
+    parse_response_head(head)
+}
+/// Splits a raw message at the first blank line. The head must be ASCII; the body is returned untouched.
+pub(crate) fn split_head(raw: &[u8]) -> Result<(&str, &[u8]), ParseError> {
+    let index = raw.windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or(ParseError::MissingHeadTerminator)?;
+    let head = core::str::from_utf8(&raw[..index])
+        .ok()
+        .filter(|head| head.is_ascii())
+        .ok_or(ParseError::NonAsciiHead)?;
+    Ok((head, &raw[index + 4..]))
+}
+/// Parses a request line plus header lines, without the trailing blank line.
+pub(crate) fn parse_request_head(head: &str) -> Result<Request<()>, ParseError> {
+    check_line_endings(head)?;
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let (method, uri, version) = parse_request_line(request_line)?;
+    let headers = parse_headers(lines)?;
+
+    let mut request = Request::new(());
+    *request.method_mut() = method;
+    *request.uri_mut() = uri;
+    *request.version_mut() = version;
+    *request.headers_mut() = headers;
+    Ok(request)
+}
+/// Parses a status line plus header lines, without the trailing blank line.
+pub(crate) fn parse_response_head(head: &str) -> Result<Response<()>, ParseError> {
+    check_line_endings(head)?;
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let (version, status) = parse_status_line(status_line)?;
+    let headers = parse_headers(lines)?;
+
+    let mut response = Response::new(());
+    *response.status_mut() = status;
+    *response.version_mut() = version;
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+/// Rejects a CR not followed by LF, or an LF not preceded by CR: peers that accept bare line
+/// endings split the head differently (request smuggling).
+fn check_line_endings(head: &str) -> Result<(), ParseError> {
+    let bytes = head.as_bytes();
+    let bare = bytes.iter().enumerate().any(|(index, &byte)| match byte {
+        b'\r' => bytes.get(index + 1) != Some(&b'\n'),
+        b'\n' => index == 0 || bytes[index - 1] != b'\r',
+        _ => false,
+    });
+    if bare {
+        return Err(ParseError::BareNewline);
+    }
+    Ok(())
+}
+/// Checks a declared `Content-Length` against the body that was actually received.
+pub(crate) fn check_declared_length(headers: &HeaderMap, actual: usize) -> Result<(), ParseError> {
+    for value in headers.get_all(header::CONTENT_LENGTH) {
+        let declared = value.to_str()
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .ok_or_else(|| ParseError::InvalidHeaderValue(header::CONTENT_LENGTH.to_string()))?;
+        if declared != actual {
+            return Err(ParseError::ContentLengthMismatch { declared, actual });
+        }
+    }
+    Ok(())
+}
+/// Exactly three tokens separated by single spaces; tabs, padding and stray CRs are rejected.
+fn parse_request_line(line: &str) -> Result<(Method, Uri, Version), ParseError> {
+    let mut parts = line.split(' ');
+    let (Some(method), Some(uri), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(ParseError::MalformedRequestLine(line.to_string()));
+    };
+    if method.is_empty() || uri.is_empty() || version.is_empty() {
+        return Err(ParseError::MalformedRequestLine(line.to_string()));
+    }
+    let method = Method::from_bytes(method.as_bytes())
+        .map_err(|_| ParseError::InvalidMethod(method.to_string()))?;
+    let uri = uri.parse::<Uri>()
+        .map_err(|_| ParseError::InvalidUri(uri.to_string()))?;
+    let version = version_from_str(version)
+        .ok_or_else(|| ParseError::InvalidVersionToken(version.to_string()))?;
+    Ok((method, uri, version))
+}
+/// The reason phrase is optional: `HTTP/1.1 204` and `HTTP/1.1 200 ` are both accepted.
+fn parse_status_line(line: &str) -> Result<(Version, StatusCode), ParseError> {
+    let mut parts = line.splitn(3, ' ');
+    let (Some(version), Some(status)) = (parts.next(), parts.next()) else {
+        return Err(ParseError::MalformedStatusLine(line.to_string()));
+    };
+    let version = version_from_str(version)
+        .ok_or_else(|| ParseError::InvalidVersionToken(version.to_string()))?;
+    let status = StatusCode::from_bytes(status.as_bytes())
+        .map_err(|_| ParseError::InvalidStatusCode(status.to_string()))?;
+    Ok((version, status))
+}
+fn parse_headers<'h>(lines: impl Iterator<Item = &'h str>) -> Result<HeaderMap, ParseError> {
+    let mut headers = HeaderMap::new();
+    for line in unfold(lines)? {
+        let line = line.as_str();
+        let (name, value) = line.split_once(':')
+            .ok_or_else(|| ParseError::MalformedHeader(line.to_string()))?;
+        if !headers::is_token(name) {
+            return Err(ParseError::InvalidHeaderName(name.to_string()));
+        }
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| ParseError::InvalidHeaderName(name.to_string()))?;
+        let value = HeaderValue::from_str(value.trim_matches([' ', '\t']))
+            .map_err(|_| ParseError::MalformedHeader(line.to_string()))?;
+        headers.append(name, value);
+    }
+    Ok(headers)
+}
+/// Joins obs-fold continuation lines (RFC 7230 §3.2.4) onto the previous header with one space.
+fn unfold<'h>(lines: impl Iterator<Item = &'h str>) -> Result<Vec<String>, ParseError> {
+    let mut unfolded: Vec<String> = Vec::new();
+    for line in lines {
+        if line.starts_with([' ', '\t']) {
+            let previous = unfolded.last_mut()
+                .ok_or_else(|| ParseError::MalformedHeader(line.to_string()))?;
+            previous.truncate(previous.trim_end_matches([' ', '\t']).len());
+            previous.push(' ');
+            previous.push_str(line.trim_matches([' ', '\t']));
+        } else {
+            unfolded.push(line.to_string());
+        }
+    }
+    Ok(unfolded)
+}
+#[cfg(test)]
+mod tests {
+    use super::parse_request_line;
+    use crate::{response_from_head, HttpParser, ParseError};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_request_str_works() {
+        let request = HttpParser::parse_request_str("GET / HTTP/1.1\r\nHost: x\r\n\r\nbody").unwrap();
+        assert_eq!(request.method(), Method::GET);
+        assert_eq!(request.uri(), "/");
+        assert_eq!(request.version(), Version::HTTP_11);
+        assert_eq!(request.headers()[header::HOST], "x");
+        assert_eq!(request.body(), "body");
+    }
+    #[test]
+    fn parse_request_str_empty_body() {
+        let request = HttpParser::parse_request_str("GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert!(request.headers().is_empty());
+        assert_eq!(request.body(), "");
+    }
+    #[test]
+    fn parse_request_str_malformed() {
+        assert_eq!(
+            HttpParser::parse_request_str("GET /\r\n\r\n").unwrap_err(),
+            ParseError::MalformedRequestLine(String::from("GET /"))
+        );
+        assert_eq!(
+            HttpParser::parse_request_str("GET  / HTTP/1.1\r\n\r\n").unwrap_err(),
+            ParseError::MalformedRequestLine(String::from("GET  / HTTP/1.1"))
+        );
+        assert_eq!(
+            HttpParser::parse_request_str("GET / HTTP/1.1\r\nHost x\r\n\r\n").unwrap_err(),
+            ParseError::MalformedHeader(String::from("Host x"))
+        );
+        assert_eq!(
+            HttpParser::parse_request_str("GET / HTTP/1.1\r\n").unwrap_err(),
+            ParseError::MissingHeadTerminator
+        );
+    }
+    #[test]
+    fn parse_request_str_round_trip() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/submit?a=1")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .header(header::ACCEPT, "text/html")
+            .header(header::ACCEPT, "text/plain")
+            .body(String::from("name=value"))
+            .unwrap();
+        let raw = HttpParser::from_request(&request).parse().unwrap();
+        let parsed = HttpParser::parse_request_str(&raw).unwrap();
+        assert_eq!(parsed.method(), request.method());
+        assert_eq!(parsed.uri(), request.uri());
+        assert_eq!(parsed.version(), request.version());
+        assert_eq!(parsed.headers(), request.headers());
+        assert_eq!(parsed.body(), request.body());
+    }
+    #[test]
+    fn parse_response_str_works() {
+        let response = HttpParser::parse_response_str("HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\n\r\nmissing").unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.version(), Version::HTTP_11);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+        assert_eq!(response.body(), "missing");
+    }
+    #[test]
+    fn parse_response_str_round_trip() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::SET_COOKIE, "a=1")
+            .header(header::SET_COOKIE, "b=2")
+            .body(String::from("<h1>hello</h1>"))
+            .unwrap();
+        let raw = HttpParser::from_response(&response).parse().unwrap();
+        let parsed = HttpParser::parse_response_str(&raw).unwrap();
+        assert_eq!(parsed.status(), response.status());
+        assert_eq!(parsed.version(), response.version());
+        assert_eq!(parsed.headers(), response.headers());
+        assert_eq!(parsed.body(), response.body());
+    }
+    #[test]
+    fn parse_response_str_malformed() {
+        assert_eq!(
+            HttpParser::parse_response_str("HTTP/1.1\r\n\r\n").unwrap_err(),
+            ParseError::MalformedStatusLine(String::from("HTTP/1.1"))
+        );
+        assert_eq!(
+            HttpParser::parse_response_str("HTTP/1.1 OK\r\n\r\n").unwrap_err(),
+            ParseError::InvalidStatusCode(String::from("OK"))
+        );
+    }
+    #[test]
+    fn parse_request_str_unfolds_obs_fold() {
+        let raw = "GET / HTTP/1.1\r\nHost: x\r\nX-Long: first part \r\n\tsecond part\r\nAccept: */*\r\n\r\n";
+        let request = HttpParser::parse_request_str(raw).unwrap();
+        assert_eq!(request.headers()["x-long"], "first part second part");
+        assert_eq!(request.headers()[header::ACCEPT], "*/*");
+        assert_eq!(
+            HttpParser::parse_request_str("GET / HTTP/1.1\r\n continued\r\n\r\n").unwrap_err(),
+            ParseError::MalformedHeader(String::from(" continued"))
+        );
+    }
+    #[test]
+    fn parse_request_line_rejects_adversarial_input() {
+        let malformed = |line: &str| ParseError::MalformedRequestLine(String::from(line));
+        let cases = [
+            ("", malformed("")),
+            ("GET", malformed("GET")),
+            ("GET /", malformed("GET /")),
+            ("GET HTTP/1.1", malformed("GET HTTP/1.1")),
+            ("GET\t/\tHTTP/1.1", malformed("GET\t/\tHTTP/1.1")),
+            ("GET /\tHTTP/1.1", malformed("GET /\tHTTP/1.1")),
+            ("GET  / HTTP/1.1", malformed("GET  / HTTP/1.1")),
+            (" GET / HTTP/1.1", malformed(" GET / HTTP/1.1")),
+            ("GET / HTTP/1.1 ", malformed("GET / HTTP/1.1 ")),
+            ("GET / HTTP/1.1 extra", malformed("GET / HTTP/1.1 extra")),
+            ("GET / HTTP/1.11", ParseError::InvalidVersionToken(String::from("HTTP/1.11"))),
+            ("GET / http/1.1", ParseError::InvalidVersionToken(String::from("http/1.1"))),
+            ("GET / HTTP/1.1\r", ParseError::InvalidVersionToken(String::from("HTTP/1.1\r"))),
+            ("G\u{e9}T / HTTP/1.1", ParseError::InvalidMethod(String::from("G\u{e9}T"))),
+            ("GE(T / HTTP/1.1", ParseError::InvalidMethod(String::from("GE(T"))),
+            ("GET /a\u{7f}b HTTP/1.1", ParseError::InvalidUri(String::from("/a\u{7f}b"))),
+        ];
+        for (line, error) in cases {
+            assert_eq!(parse_request_line(line).unwrap_err(), error, "{line:?}");
+        }
+    }
+    #[test]
+    fn parse_request_str_limited_rejects_header_bombs() {
+        let mut raw = String::from("GET / HTTP/1.1\r\n");
+        while raw.len() < 10 * 1024 {
+            raw.push_str("x-filler: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n");
+        }
+        raw.push_str("\r\n");
+        assert_eq!(HttpParser::parse_request_str_limited(&raw, 4096).unwrap_err(), ParseError::HeadersTooLarge);
+        assert!(HttpParser::parse_request_str_limited(&raw, 16 * 1024).is_ok());
+
+        let request = HttpParser::parse_request_str_limited("GET / HTTP/1.1\r\nHost: x\r\n\r\nbody", 4096).unwrap();
+        assert_eq!(request.headers()[header::HOST], "x");
+        assert_eq!(request.body(), "body");
+        assert_eq!(
+            HttpParser::parse_request_str_limited("GET / HTTP/1.1\r\n", 4096).unwrap_err(),
+            ParseError::MissingHeadTerminator
+        );
+    }
+    #[test]
+    fn parse_response_str_accepts_missing_reason() {
+        let cases = [
+            ("HTTP/1.1 204\r\n\r\n", StatusCode::NO_CONTENT),
+            ("HTTP/1.1 200 \r\n\r\n", StatusCode::OK),
+            ("HTTP/1.1 200 OK\r\n\r\n", StatusCode::OK),
+            ("HTTP/1.0 404 Gone Fishing\r\n\r\n", StatusCode::NOT_FOUND),
+        ];
+        for (raw, status) in cases {
+            assert_eq!(HttpParser::parse_response_str(raw).unwrap().status(), status, "{raw:?}");
+        }
+    }
+    #[test]
+    fn parse_request_str_rejects_non_token_header_names() {
+        let request = HttpParser::parse_request_str("GET / HTTP/1.1\r\nX-Good-1: v\r\n\r\n").unwrap();
+        assert_eq!(request.headers()["x-good-1"], "v");
+        for (raw, name) in [
+            ("GET / HTTP/1.1\r\nX Bad: 1\r\n\r\n", "X Bad"),
+            ("GET / HTTP/1.1\r\nX-Bad : 1\r\n\r\n", "X-Bad "),
+            ("GET / HTTP/1.1\r\nX\x01Bad: 1\r\n\r\n", "X\x01Bad"),
+            ("GET / HTTP/1.1\r\n: 1\r\n\r\n", ""),
+        ] {
+            assert_eq!(
+                HttpParser::parse_request_str(raw).unwrap_err(),
+                ParseError::InvalidHeaderName(String::from(name)),
+                "{raw:?}"
+            );
+        }
+    }
+    #[test]
+    fn response_from_head_parses_headers() {
+        let head = "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nCache-Control: no-store\r\nVary: accept\r\nVary: origin\r\n\r\n";
+        let response = response_from_head(head).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.version(), Version::HTTP_11);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-store");
+        assert_eq!(response.headers().get_all(header::VARY).iter().count(), 2);
+        assert_eq!(response_from_head(head.trim_end()).unwrap().headers().len(), 4);
+    }
+    #[test]
+    fn head_parsers_reject_bare_newlines() {
+        assert_eq!(
+            HttpParser::parse_request_str("GET / HTTP/1.1\r\nHost: x\nX-Smuggled: 1\r\n\r\n").unwrap_err(),
+            ParseError::BareNewline
+        );
+        assert_eq!(
+            HttpParser::parse_response_str("HTTP/1.1 200 OK\rX-Bad: 1\r\n\r\n").unwrap_err(),
+            ParseError::BareNewline
+        );
+        let request = HttpParser::parse_request_str("GET / HTTP/1.1\r\nHost: x\r\nAccept: */*\r\n\r\nbody\n").unwrap();
+        assert_eq!(request.headers().len(), 2);
+        assert_eq!(request.body(), "body\n");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{HttpMessage, ParseError};
+use http::{header, HeaderMap, HeaderName};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Compares two serialized messages, ignoring header order, `Date`, and the `ignore` headers.
+/// Returns `None` when they are equivalent, otherwise one `-`/`+` line pair per difference.
+pub fn message_diff(a: &str, b: &str, ignore: &[HeaderName]) -> Result<Option<String>, ParseError> {
+    let (a, b) = (a.parse::<HttpMessage>()?, b.parse::<HttpMessage>()?);
+    let (a, b) = (a.parser(), b.parser());
+    let mut diff = String::new();
+    let (start_a, start_b) = (a.start_line()?, b.start_line()?);
+    if start_a != start_b {
+        diff.push_str(&format!("- {start_a}\n+ {start_b}\n"));
+    }
+    let (headers_a, headers_b) = (header_lines(a.headers(), ignore), header_lines(b.headers(), ignore));
+    let (removed, added) = multiset_difference(&headers_a, &headers_b);
+    for line in removed {
+        diff.push_str(&format!("- {line}\n"));
+    }
+    for line in added {
+        diff.push_str(&format!("+ {line}\n"));
+    }
+    let (body_a, body_b) = (String::from_utf8_lossy(a.body_bytes()), String::from_utf8_lossy(b.body_bytes()));
+    if body_a != body_b {
+        diff.push_str(&format!("- body {body_a:?}\n+ body {body_b:?}\n"));
+    }
+    Ok(Some(diff).filter(|diff| !diff.is_empty()))
+}
+/// `name:value` lines, sorted so that order doesn't matter but repeated values still count.
+fn header_lines(headers: &HeaderMap, ignore: &[HeaderName]) -> Vec<String> {
+    let mut lines = headers.iter()
+        .filter(|(name, _)| **name != header::DATE && !ignore.contains(name))
+        .map(|(name, value)| format!("{name}:{}", String::from_utf8_lossy(value.as_bytes())))
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines
+}
+/// Walks two sorted lists side by side and returns the lines only in `a` and only in `b`,
+/// counting each copy of a repeated line separately.
+fn multiset_difference<'l>(a: &'l [String], b: &'l [String]) -> (Vec<&'l String>, Vec<&'l String>) {
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut a, mut b) = (a.iter().peekable(), b.iter().peekable());
+    loop {
+        match (a.peek(), b.peek()) {
+            (Some(line_a), Some(line_b)) if line_a == line_b => {
+                a.next();
+                b.next();
+            }
+            (Some(line_a), Some(line_b)) if line_a < line_b => removed.extend(a.next()),
+            (Some(_), Some(_)) | (None, Some(_)) => added.extend(b.next()),
+            (Some(_), None) => removed.extend(a.next()),
+            (None, None) => return (removed, added),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::message_diff;
+    use http::header;
+
+    #[test]
+    fn message_diff_ignores_header_order() {
+        let a = "HTTP/1.1 200 OK\r\ncontent-type:text/plain\r\nx-id:1\r\ndate:Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\nhi";
+        let b = "HTTP/1.1 200 OK\r\nx-id:1\r\ndate:Mon, 07 Nov 1994 08:49:37 GMT\r\ncontent-type:text/plain\r\n\r\nhi";
+        assert_eq!(message_diff(a, b, &[]).unwrap(), None);
+    }
+    #[test]
+    fn message_diff_reports_differences() {
+        let a = "GET /a HTTP/1.1\r\nhost:example.com\r\nx-trace:1\r\n\r\n";
+        let b = "GET /b HTTP/1.1\r\nhost:example.org\r\nx-trace:2\r\n\r\nbody";
+        assert_eq!(
+            message_diff(a, b, &[header::HeaderName::from_static("x-trace")]).unwrap().unwrap(),
+            "- GET /a HTTP/1.1\n+ GET /b HTTP/1.1\n- host:example.com\n+ host:example.org\n- body \"\"\n+ body \"body\"\n"
+        );
+        assert!(message_diff("not a message", b, &[]).is_err());
+    }
+    #[test]
+    fn message_diff_counts_duplicate_headers() {
+        let a = "HTTP/1.1 200 OK\r\nset-cookie:a=1\r\nset-cookie:a=1\r\nvary:accept\r\n\r\n";
+        let b = "HTTP/1.1 200 OK\r\nvary:accept\r\nset-cookie:a=1\r\n\r\n";
+        assert_eq!(message_diff(a, b, &[]).unwrap().unwrap(), "- set-cookie:a=1\n");
+        assert_eq!(message_diff(b, a, &[]).unwrap().unwrap(), "+ set-cookie:a=1\n");
+        assert_eq!(message_diff(a, a, &[]).unwrap(), None);
+    }
+}
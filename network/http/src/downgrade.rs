@@ -0,0 +1,71 @@
+use crate::{chunked, reason_phrase, uri_form, HttpParser, Message, ParseError};
+use http::{header, HeaderName};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Display;
+
+/// Headers an HTTP/1.0 peer can't act on.
+const HTTP11_ONLY: [HeaderName; 4] = [header::TE, header::TRAILER, header::UPGRADE, header::EXPECT];
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes as HTTP/1.0: the version token becomes `HTTP/1.0`, a chunked body is sent
+    /// whole with a `Content-Length`, and headers only HTTP/1.1 defines are dropped.
+    pub fn parse_http10_downgrade(&self) -> Result<String, ParseError> {
+        let body = self.body_string();
+        let mut headers = self.headers().clone();
+        if chunked::is_chunked(&headers) {
+            chunked::unchunk_headers(&mut headers, body.len());
+        }
+        for name in &HTTP11_ONLY {
+            headers.remove(name);
+        }
+        let start_line = match &self.message {
+            Message::Request(request) => {
+                let target = uri_form::default_target(request.method(), request.uri());
+                format!("{} {target} HTTP/1.0", request.method())
+            }
+            Message::Response(response) => {
+                let status = response.status();
+                format!("HTTP/1.0 {} {}", status.as_str(), reason_phrase(status))
+            }
+        };
+        let head = self.assemble_head(&start_line, &headers);
+        Ok(Self::join_head(head, &body))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::HttpParser;
+    use http::{header, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_http10_downgrade_unchunks_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .header(header::TRAILER, "Expires")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body("hello")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_http10_downgrade().unwrap(),
+            "HTTP/1.0 200 OK\r\ncontent-type:text/plain\r\ncontent-length:5\r\n\r\nhello"
+        );
+    }
+    #[test]
+    fn parse_http10_downgrade_drops_http11_headers() {
+        let request = Request::builder()
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "example.com")
+            .header(header::EXPECT, "100-continue")
+            .header(header::UPGRADE, "websocket")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_http10_downgrade().unwrap(),
+            "GET / HTTP/1.0\r\nhost:example.com\r\n\r\n"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{HttpParser, ParseError};
+use http::Response;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+/// Writes an interim `hints` response (e.g. `103 Early Hints` with `Link` headers) followed by
+/// `final_resp`, as both would go out on one connection. `hints` must have a 1xx status.
+pub fn serialize_with_early_hints<T: Display>(
+    hints: &Response<()>,
+    final_resp: &Response<T>,
+) -> Result<String, ParseError> {
+    if !hints.status().is_informational() {
+        return Err(ParseError::InvalidStatusCode(hints.status().as_str().to_string()));
+    }
+    let mut out = String::from_utf8_lossy(&HttpParser::from_response(hints).head_bytes()?).into_owned();
+    out.push_str(&HttpParser::from_response(final_resp).parse()?);
+    Ok(out)
+}
+#[cfg(test)]
+mod tests {
+    use crate::{serialize_with_early_hints, ParseError};
+    use http::{header, Response, StatusCode, Version};
+
+    #[test]
+    fn serialize_with_early_hints_writes_both_responses() {
+        let hints = Response::builder()
+            .status(103)
+            .version(Version::HTTP_11)
+            .header(header::LINK, "</style.css>; rel=preload; as=style")
+            .body(())
+            .unwrap();
+        let final_resp = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "2")
+            .body("hi")
+            .unwrap();
+        let out = serialize_with_early_hints(&hints, &final_resp).unwrap();
+        assert_eq!(
+            out,
+            "HTTP/1.1 103 Early Hints\r\nlink:</style.css>; rel=preload; as=style\r\n\r\n\
+             HTTP/1.1 200 OK\r\ncontent-length:2\r\n\r\nhi"
+        );
+        assert_eq!(out.matches("HTTP/1.1 ").count(), 2);
+    }
+    #[test]
+    fn serialize_with_early_hints_rejects_final_hints() {
+        let hints = Response::builder().status(StatusCode::OK).body(()).unwrap();
+        let final_resp = Response::builder().status(StatusCode::OK).body("").unwrap();
+        assert_eq!(
+            serialize_with_early_hints(&hints, &final_resp).unwrap_err(),
+            ParseError::InvalidStatusCode(String::from("200"))
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{HttpParser, ParseError};
+use http::Version;
+use bytes::{BufMut, BytesMut};
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// Appends the message to `dst` piece by piece with `put_slice`, as a
+    /// `tokio_util::codec::Encoder` would. Produces the same bytes as `parse_bytes()`.
+    pub fn encode(&self, dst: &mut BytesMut) -> Result<(), ParseError> {
+        dst.reserve(self.serialized_len()?);
+        if self.version() == Version::HTTP_09 {
+            dst.put_slice(&self.simple_head());
+            dst.put_slice(self.body_bytes());
+            return Ok(());
+        }
+        let eol = self.line_ending.as_str().as_bytes();
+        dst.put_slice(self.parse_start_line()?.as_bytes());
+        dst.put_slice(eol);
+        for (name, value) in self.headers() {
+            dst.put_slice(self.header_name(name).as_bytes());
+            dst.put_u8(b':');
+            dst.put_slice(value.as_bytes());
+            dst.put_slice(eol);
+        }
+        dst.put_slice(eol);
+        dst.put_slice(self.body_bytes());
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::HttpParser;
+    use bytes::BytesMut;
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn encode_matches_parse_bytes() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header(header::SET_COOKIE, "a=1")
+            .header(header::SET_COOKIE, "b=2")
+            .body(vec![0x00, 0xff, 0x7f])
+            .unwrap();
+        let request = Request::builder().method(Method::GET).uri("/old").version(Version::HTTP_09).body("").unwrap();
+        let mut dst = BytesMut::from(&b"prefix"[..]);
+        HttpParser::from_response(&response).encode(&mut dst).unwrap();
+        HttpParser::from_request(&request).encode(&mut dst).unwrap();
+
+        let mut expected = b"prefix".to_vec();
+        expected.extend(HttpParser::from_response(&response).parse_bytes().unwrap());
+        expected.extend(HttpParser::from_request(&request).parse_bytes().unwrap());
+        assert_eq!(&dst[..], &expected[..]);
+    }
+}
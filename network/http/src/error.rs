@@ -0,0 +1,87 @@
+use http::Version;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnsupportedVersion(Version),
+    WrongVariant,
+    MissingHeadTerminator,
+    NonAsciiHead,
+    MalformedRequestLine(String),
+    MalformedStatusLine(String),
+    InvalidMethod(String),
+    InvalidUri(String),
+    InvalidVersionToken(String),
+    InvalidStatusCode(String),
+    MalformedHeader(String),
+    InvalidHeaderName(String),
+    InvalidHeaderValue(String),
+    ContentLengthMismatch {
+        declared: usize,
+        actual: usize,
+    },
+    MissingHost,
+    NotChunked,
+    UndeclaredTrailer(String),
+    Compression(String),
+    InvalidMessage(String),
+    MissingPseudoHeader(String),
+    UnframedBody(usize),
+    HeadersTooLarge,
+    ConflictingFraming,
+    InvalidRange(String),
+    BodyTooLarge {
+        size: usize,
+        max: usize,
+    },
+    BufferTooSmall {
+        needed: usize,
+    },
+    BareNewline,
+    BodyFormat,
+    DecodedBodyTooLarge {
+        max: usize,
+    },
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::UnsupportedVersion(version) => write!(f, "unsupported version: {version:?}"),
+            ParseError::WrongVariant => write!(f, "operation does not apply to this message variant"),
+            ParseError::MissingHeadTerminator => write!(f, "missing blank line after message head"),
+            ParseError::NonAsciiHead => write!(f, "message head is not ASCII"),
+            ParseError::MalformedRequestLine(line) => write!(f, "malformed request line: {line:?}"),
+            ParseError::MalformedStatusLine(line) => write!(f, "malformed status line: {line:?}"),
+            ParseError::InvalidMethod(method) => write!(f, "invalid method: {method:?}"),
+            ParseError::InvalidUri(uri) => write!(f, "invalid uri: {uri:?}"),
+            ParseError::InvalidVersionToken(version) => write!(f, "invalid version token: {version:?}"),
+            ParseError::InvalidStatusCode(status) => write!(f, "invalid status code: {status:?}"),
+            ParseError::MalformedHeader(line) => write!(f, "malformed header line: {line:?}"),
+            ParseError::InvalidHeaderName(name) => write!(f, "invalid header name: {name:?}"),
+            ParseError::InvalidHeaderValue(name) => write!(f, "invalid value for header {name:?}"),
+            ParseError::ContentLengthMismatch { declared, actual } => {
+                write!(f, "content-length is {declared} but the body is {actual} bytes")
+            }
+            ParseError::MissingHost => write!(f, "HTTP/1.1 request has no Host header"),
+            ParseError::NotChunked => write!(f, "message does not use chunked transfer-encoding"),
+            ParseError::UndeclaredTrailer(name) => write!(f, "trailer {name:?} is not declared in the Trailer header"),
+            ParseError::Compression(error) => write!(f, "compression failed: {error}"),
+            ParseError::InvalidMessage(error) => write!(f, "invalid message: {error}"),
+            ParseError::MissingPseudoHeader(name) => write!(f, "HTTP/2 request has no {name} pseudo-header"),
+            ParseError::UnframedBody(index) => {
+                write!(f, "request {index} has a body not framed by content-length or a complete chunked encoding")
+            }
+            ParseError::HeadersTooLarge => write!(f, "message head exceeds the size limit"),
+            ParseError::ConflictingFraming => write!(f, "message has both content-length and transfer-encoding"),
+            ParseError::InvalidRange(range) => write!(f, "invalid byte range: {range:?}"),
+            ParseError::BodyTooLarge { size, max } => write!(f, "body is {size} bytes but the limit is {max}"),
+            ParseError::BufferTooSmall { needed } => write!(f, "buffer too small: {needed} bytes needed"),
+            ParseError::BodyFormat => write!(f, "the body's Display implementation returned an error"),
+            ParseError::DecodedBodyTooLarge { max } => write!(f, "decoded body exceeds {max} bytes"),
+            ParseError::BareNewline => write!(f, "message head contains a CR or LF outside a CRLF pair"),
+        }
+    }
+}
+impl Error for ParseError {}
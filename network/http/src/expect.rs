@@ -0,0 +1,105 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderValue};
+#[cfg(feature = "std")]
+use http::{Response, StatusCode};
+use alloc::string::String;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+impl<T: Display> HttpParser<'_, T> {
+    /// The request head with `Expect: 100-continue` added if missing; send `parse_body_only()`
+    /// once the server answers `100 Continue`.
+    pub fn parse_expect_continue_head(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let mut headers = request.headers().clone();
+        if !headers.contains_key(header::EXPECT) {
+            headers.insert(header::EXPECT, HeaderValue::from_static("100-continue"));
+        }
+        let head = self.head_bytes_with(&headers)?;
+        Ok(String::from_utf8_lossy(&head).into_owned())
+    }
+    /// Just the body, exactly as `parse()` would write it after the head.
+    pub fn parse_body_only(&self) -> String {
+        self.body_string()
+    }
+}
+/// Writes the `100 Continue` interim response and then `final_`, for a server answering a
+/// request that sent `Expect: 100-continue`. Fails with `InvalidInput` for any other interim status.
+#[cfg(feature = "std")]
+pub fn write_continue_then_final<W: Write, T: AsRef<[u8]>>(
+    w: &mut W,
+    interim: &Response<()>,
+    final_: &Response<T>,
+) -> io::Result<()> {
+    if interim.status() != StatusCode::CONTINUE {
+        let error = ParseError::InvalidStatusCode(interim.status().as_str().into());
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+    }
+    let head = HttpParser::from_response(interim)
+        .head_bytes()
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    w.write_all(&head)?;
+    HttpParser::from_response(final_).write_to(w)
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_expect_continue_head_injects_expect() {
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/upload")
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "5")
+            .body("hello")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let head = parser.parse_expect_continue_head().unwrap();
+        assert_eq!(head, "PUT /upload HTTP/1.1\r\ncontent-length:5\r\nexpect:100-continue\r\n\r\n");
+        assert_eq!(parser.parse_body_only(), "hello");
+    }
+    #[test]
+    fn parse_expect_continue_head_keeps_existing_expect() {
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/upload")
+            .version(Version::HTTP_11)
+            .header(header::EXPECT, "100-continue")
+            .body("hello")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let head = parser.parse_expect_continue_head().unwrap();
+        assert_eq!(head.matches("expect:").count(), 1);
+        assert_eq!(head + &parser.parse_body_only(), parser.parse().unwrap());
+
+        let response = Response::builder().status(StatusCode::OK).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_expect_continue_head().unwrap_err(),
+            ParseError::WrongVariant
+        );
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_continue_then_final_writes_both_heads() {
+        use crate::write_continue_then_final;
+
+        let interim = Response::builder().status(StatusCode::CONTINUE).body(()).unwrap();
+        let final_ = Response::builder()
+            .status(StatusCode::CREATED)
+            .header(header::CONTENT_LENGTH, "2")
+            .body("ok")
+            .unwrap();
+        let mut out = Vec::new();
+        write_continue_then_final(&mut out, &interim, &final_).unwrap();
+        assert_eq!(out, b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 201 Created\r\ncontent-length:2\r\n\r\nok");
+
+        let early_hints = Response::builder().status(103).body(()).unwrap();
+        let error = write_continue_then_final(&mut Vec::new(), &early_hints, &final_).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::ParseError;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// An RFC 8187 extended value such as `UTF-8''na%C3%AFve%20file.txt`, for `filename*=` and other
+/// `*` parameters. `ISO-8859-1` is transcoded and fails on chars it cannot hold; other charsets fail.
+pub fn encode_ext_value(charset: &str, value: &str) -> Result<String, ParseError> {
+    let unencodable = || ParseError::InvalidMessage(format!("cannot encode {value:?} as {charset}"));
+    let bytes = if charset.eq_ignore_ascii_case("UTF-8") {
+        value.as_bytes().to_vec()
+    } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+        value.chars().map(u8::try_from).collect::<Result<Vec<_>, _>>().map_err(|_| unencodable())?
+    } else {
+        return Err(unencodable());
+    };
+    let mut encoded = format!("{charset}''");
+    for byte in bytes {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    Ok(encoded)
+}
+/// Decodes an RFC 8187 extended value in `UTF-8` or `ISO-8859-1`; the language tag is ignored.
+pub fn decode_ext_value(ext_value: &str) -> Result<String, ParseError> {
+    let invalid = || ParseError::InvalidMessage(format!("invalid extended value: {ext_value:?}"));
+    let mut parts = ext_value.splitn(3, '\'');
+    let (Some(charset), Some(_language), Some(encoded)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut input = encoded.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hex = [input.next().ok_or_else(invalid)?, input.next().ok_or_else(invalid)?];
+        if !hex.iter().all(u8::is_ascii_hexdigit) {
+            return Err(invalid());
+        }
+        let hex = core::str::from_utf8(&hex).map_err(|_| invalid())?;
+        bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+    }
+    if charset.eq_ignore_ascii_case("UTF-8") {
+        String::from_utf8(bytes).map_err(|_| invalid())
+    } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+        Ok(bytes.into_iter().map(char::from).collect())
+    } else {
+        Err(invalid())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{decode_ext_value, encode_ext_value};
+
+    #[test]
+    fn ext_value_round_trips_unicode() {
+        let encoded = encode_ext_value("UTF-8", "naïve résumé 2.pdf").unwrap();
+        assert_eq!(encoded, "UTF-8''na%C3%AFve%20r%C3%A9sum%C3%A9%202.pdf");
+        assert_eq!(decode_ext_value(&encoded).unwrap(), "naïve résumé 2.pdf");
+
+        let latin = encode_ext_value("ISO-8859-1", "£ rates").unwrap();
+        assert_eq!(latin, "ISO-8859-1''%A3%20rates");
+        assert_eq!(decode_ext_value(&latin).unwrap(), "£ rates");
+        assert_eq!(decode_ext_value("utf-8'en'%E2%82%AC%20rates").unwrap(), "€ rates");
+    }
+    #[test]
+    fn encode_ext_value_rejects_unencodable_input() {
+        assert!(encode_ext_value("ISO-8859-1", "€ rates").is_err());
+        assert!(encode_ext_value("KOI8-R", "abc").is_err());
+    }
+    #[test]
+    fn decode_ext_value_rejects_malformed_input() {
+        assert!(decode_ext_value("UTF-8''bad%2").is_err());
+        assert!(decode_ext_value("UTF-8''bad%+A").is_err());
+        assert!(decode_ext_value("UTF-8''%FF").is_err());
+        assert!(decode_ext_value("no-quotes").is_err());
+        assert!(decode_ext_value("KOI8-R''abc").is_err());
+    }
+}
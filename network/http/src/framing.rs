@@ -0,0 +1,136 @@
+use crate::{chunked, HttpParser, Message};
+use http::{header, Method, StatusCode};
+
+/// How the receiver finds the end of a message body (RFC 7230 §3.3.3).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramingMode {
+    ContentLength(u64),
+    Chunked,
+    UntilClose,
+}
+/// How many body bytes a reader should consume, as `expected_body_length()` decides it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyLength {
+    Exact(u64),
+    Chunked,
+    UntilClose,
+    /// The message cannot have a body: a 1xx, 204 or 304 response, or a response to HEAD.
+    None,
+}
+/// 1xx, 204 and 304 responses never carry a body, whatever their framing headers say.
+pub(crate) fn is_bodiless(status: StatusCode) -> bool {
+    status.is_informational() || status == StatusCode::NO_CONTENT || status == StatusCode::NOT_MODIFIED
+}
+impl<T> HttpParser<'_, T> {
+    /// The body framing implied by the headers and status. A request without framing headers
+    /// has no body; a response without them runs until the connection closes.
+    pub fn framing_mode(&self) -> FramingMode {
+        if let Message::Response(response) = &self.message && is_bodiless(response.status()) {
+            return FramingMode::ContentLength(0);
+        }
+        let headers = self.headers();
+        if headers.contains_key(header::TRANSFER_ENCODING) {
+            return match (chunked::is_chunked(headers), &self.message) {
+                (true, _) => FramingMode::Chunked,
+                (false, Message::Request(_)) => FramingMode::ContentLength(0),
+                (false, Message::Response(_)) => FramingMode::UntilClose,
+            };
+        }
+        let declared = headers.get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        match (declared, &self.message) {
+            (Some(length), _) => FramingMode::ContentLength(length),
+            (None, Message::Request(_)) => FramingMode::ContentLength(0),
+            (None, Message::Response(_)) => FramingMode::UntilClose,
+        }
+    }
+    /// Like `framing_mode()` for a response to a `method` request: a response to HEAD never has a body.
+    pub fn framing_mode_for(&self, method: &Method) -> FramingMode {
+        match &self.message {
+            Message::Response(_) if method == Method::HEAD => FramingMode::ContentLength(0),
+            _ => self.framing_mode(),
+        }
+    }
+    /// The body length a reader should expect, from `framing_mode()`. A request without
+    /// framing headers has `Exact(0)`.
+    pub fn expected_body_length(&self) -> BodyLength {
+        if let Message::Response(response) = &self.message && is_bodiless(response.status()) {
+            return BodyLength::None;
+        }
+        match self.framing_mode() {
+            FramingMode::ContentLength(length) => BodyLength::Exact(length),
+            FramingMode::Chunked => BodyLength::Chunked,
+            FramingMode::UntilClose => BodyLength::UntilClose,
+        }
+    }
+    /// Like `expected_body_length()` for a response to a `method` request.
+    pub fn expected_body_length_for(&self, method: &Method) -> BodyLength {
+        match &self.message {
+            Message::Response(_) if method == Method::HEAD => BodyLength::None,
+            _ => self.expected_body_length(),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{BodyLength, FramingMode, HttpParser};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    fn response(status: StatusCode, headers: &[(header::HeaderName, &str)]) -> Response<String> {
+        let mut builder = Response::builder().status(status).version(Version::HTTP_11);
+        for (name, value) in headers {
+            builder = builder.header(name, *value);
+        }
+        builder.body(String::new()).unwrap()
+    }
+
+    #[test]
+    fn framing_mode_follows_headers() {
+        let sized = response(StatusCode::OK, &[(header::CONTENT_LENGTH, "42")]);
+        assert_eq!(HttpParser::from_response(&sized).framing_mode(), FramingMode::ContentLength(42));
+
+        let chunked = response(
+            StatusCode::OK,
+            &[(header::TRANSFER_ENCODING, "gzip, chunked"), (header::CONTENT_LENGTH, "42")],
+        );
+        assert_eq!(HttpParser::from_response(&chunked).framing_mode(), FramingMode::Chunked);
+
+        let unframed = response(StatusCode::OK, &[]);
+        assert_eq!(HttpParser::from_response(&unframed).framing_mode(), FramingMode::UntilClose);
+
+        let gzip_only = response(StatusCode::OK, &[(header::TRANSFER_ENCODING, "gzip")]);
+        assert_eq!(HttpParser::from_response(&gzip_only).framing_mode(), FramingMode::UntilClose);
+
+        let request = Request::builder().uri("/").body("").unwrap();
+        assert_eq!(HttpParser::from_request(&request).framing_mode(), FramingMode::ContentLength(0));
+    }
+    #[test]
+    fn framing_mode_bodyless_responses() {
+        for status in [StatusCode::CONTINUE, StatusCode::NO_CONTENT, StatusCode::NOT_MODIFIED] {
+            let response = response(status, &[(header::CONTENT_LENGTH, "42")]);
+            assert_eq!(HttpParser::from_response(&response).framing_mode(), FramingMode::ContentLength(0));
+        }
+
+        let head = response(StatusCode::OK, &[(header::CONTENT_LENGTH, "42")]);
+        let parser = HttpParser::from_response(&head);
+        assert_eq!(parser.framing_mode_for(&Method::HEAD), FramingMode::ContentLength(0));
+        assert_eq!(parser.framing_mode_for(&Method::GET), FramingMode::ContentLength(42));
+    }
+    #[test]
+    fn expected_body_length_per_framing() {
+        let no_content = response(StatusCode::NO_CONTENT, &[(header::CONTENT_LENGTH, "42")]);
+        assert_eq!(HttpParser::from_response(&no_content).expected_body_length(), BodyLength::None);
+        let chunked = response(StatusCode::OK, &[(header::TRANSFER_ENCODING, "chunked")]);
+        assert_eq!(HttpParser::from_response(&chunked).expected_body_length(), BodyLength::Chunked);
+        let sized = response(StatusCode::OK, &[(header::CONTENT_LENGTH, "42")]);
+        let parser = HttpParser::from_response(&sized);
+        assert_eq!(parser.expected_body_length(), BodyLength::Exact(42));
+        assert_eq!(parser.expected_body_length_for(&Method::HEAD), BodyLength::None);
+        let unframed = response(StatusCode::OK, &[]);
+        assert_eq!(HttpParser::from_response(&unframed).expected_body_length(), BodyLength::UntilClose);
+
+        let request = Request::builder().method(Method::HEAD).uri("/").body("").unwrap();
+        assert_eq!(HttpParser::from_request(&request).expected_body_length_for(&Method::HEAD), BodyLength::Exact(0));
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{headers, HttpParser, Message, ParseError};
+use http::{header, HeaderName, Method, Version};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+const FRAME_DATA: u8 = 0x0;
+const FRAME_HEADERS: u8 = 0x1;
+const FRAME_CONTINUATION: u8 = 0x9;
+const FLAG_END_STREAM: u8 = 0x1;
+const FLAG_END_HEADERS: u8 = 0x4;
+const MAX_FRAME_SIZE: usize = 16384;
+const STREAM_ID: u32 = 1;
+const PROXY_CONNECTION: HeaderName = HeaderName::from_static("proxy-connection");
+
+impl<T> HttpParser<'_, T> {
+    fn pseudo_headers(&self) -> Vec<(&'static str, String)> {
+        match &self.message {
+            Message::Request(request) => {
+                let uri = request.uri();
+                let scheme = uri.scheme_str().unwrap_or("https");
+                let authority = uri.authority()
+                    .map(|authority| authority.to_string())
+                    .or_else(|| {
+                        request.headers()
+                            .get(header::HOST)
+                            .and_then(|host| host.to_str().ok())
+                            .map(String::from)
+                    })
+                    .unwrap_or_default();
+                if request.method() == Method::CONNECT {
+                    return vec![(":method", request.method().to_string()), (":authority", authority)];
+                }
+                let path = uri.path_and_query()
+                    .map(|path| path.to_string())
+                    .unwrap_or_else(|| String::from("/"));
+                vec![
+                    (":method", request.method().to_string()),
+                    (":scheme", scheme.to_string()),
+                    (":authority", authority),
+                    (":path", path),
+                ]
+            }
+            Message::Response(response) => {
+                vec![(":status", response.status().as_str().to_string())]
+            }
+        }
+    }
+    /// The regular header fields HTTP/2 allows (RFC 9113 §8.2.2): hop-by-hop headers and
+    /// `Proxy-Connection` are dropped, and `TE` is kept only as `trailers`.
+    fn h2_fields(&self) -> Vec<(&str, &[u8])> {
+        let mut fields = Vec::with_capacity(self.headers().len());
+        for (name, value) in self.headers() {
+            if name == header::TE {
+                let trailers = value.to_str()
+                    .is_ok_and(|value| value.split(',').any(|coding| coding.trim().eq_ignore_ascii_case("trailers")));
+                if trailers {
+                    fields.push((name.as_str(), &b"trailers"[..]));
+                }
+            } else if !headers::HOP_BY_HOP.contains(name) && name != PROXY_CONNECTION {
+                fields.push((name.as_str(), value.as_bytes()));
+            }
+        }
+        fields
+    }
+    /// An HTTP/2 request needs every pseudo-header; `:authority` has no default to fall back on.
+    pub(crate) fn check_pseudo_headers(&self) -> Result<(), ParseError> {
+        if self.version() != Version::HTTP_2 {
+            return Ok(());
+        }
+        match self.pseudo_headers().into_iter().find(|(_, value)| value.is_empty()) {
+            Some((name, _)) => Err(ParseError::MissingPseudoHeader(name.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// Encodes the message as an HTTP/2 HEADERS frame (plus CONTINUATION frames if needed)
+    /// followed by DATA frames on stream 1. Header fields use HPACK literals without
+    /// indexing or Huffman coding, so no encoder state is required.
+    pub fn parse_h2(&self) -> core::result::Result<Vec<u8>, ParseError> {
+        let version = self.version();
+        if version != Version::HTTP_2 {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+        let mut block = Vec::new();
+        for (name, value) in self.pseudo_headers() {
+            encode_literal(&mut block, name.as_bytes(), value.as_bytes());
+        }
+        for (name, value) in self.h2_fields() {
+            encode_literal(&mut block, name.as_bytes(), value);
+        }
+
+        let body = self.body_bytes();
+        let mut bytes = Vec::new();
+        let mut fragments = block.chunks(MAX_FRAME_SIZE).peekable();
+        let mut frame_type = FRAME_HEADERS;
+        loop {
+            let fragment = fragments.next().unwrap_or_default();
+            let mut flags = 0;
+            if frame_type == FRAME_HEADERS && body.is_empty() {
+                flags |= FLAG_END_STREAM;
+            }
+            if fragments.peek().is_none() {
+                flags |= FLAG_END_HEADERS;
+            }
+            write_frame(&mut bytes, frame_type, flags, fragment);
+            if fragments.peek().is_none() {
+                break;
+            }
+            frame_type = FRAME_CONTINUATION;
+        }
+        let mut chunks = body.chunks(MAX_FRAME_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            let flags = if chunks.peek().is_none() { FLAG_END_STREAM } else { 0 };
+            write_frame(&mut bytes, FRAME_DATA, flags, chunk);
+        }
+        Ok(bytes)
+    }
+}
+impl<T: Display> HttpParser<'_, T> {
+    /// A readable dump in the style of `nghttp -v`: pseudo-headers first, then the regular
+    /// headers as `name: value`, then the body after a blank line. Not wire-accurate HPACK.
+    pub fn parse_h2_debug(&self) -> Result<String, ParseError> {
+        let version = self.version();
+        if version != Version::HTTP_2 {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+        let mut dump = String::new();
+        for (name, value) in self.pseudo_headers() {
+            dump.push_str(&format!("{name}: {value}\n"));
+        }
+        for (name, value) in self.h2_fields() {
+            dump.push_str(&format!("{name}: {}\n", String::from_utf8_lossy(value)));
+        }
+        dump.push('\n');
+        dump.push_str(&self.body_string());
+        Ok(dump)
+    }
+}
+fn write_frame(bytes: &mut Vec<u8>, frame_type: u8, flags: u8, payload: &[u8]) {
+    let length = payload.len() as u32;
+    bytes.extend_from_slice(&length.to_be_bytes()[1..]);
+    bytes.push(frame_type);
+    bytes.push(flags);
+    bytes.extend_from_slice(&STREAM_ID.to_be_bytes());
+    bytes.extend_from_slice(payload);
+}
+fn encode_literal(block: &mut Vec<u8>, name: &[u8], value: &[u8]) {
+    block.push(0x00);
+    encode_string(block, name);
+    encode_string(block, value);
+}
+fn encode_string(block: &mut Vec<u8>, string: &[u8]) {
+    encode_integer(block, string.len(), 7);
+    block.extend_from_slice(string);
+}
+fn encode_integer(block: &mut Vec<u8>, mut value: usize, prefix_bits: u32) {
+    let max_prefix = (1 << prefix_bits) - 1;
+    if value < max_prefix {
+        block.push(value as u8);
+        return;
+    }
+    block.push(max_prefix as u8);
+    value -= max_prefix;
+    while value >= 0x80 {
+        block.push((value % 0x80 + 0x80) as u8);
+        value /= 0x80;
+    }
+    block.push(value as u8);
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    struct Frame {
+        frame_type: u8,
+        flags: u8,
+        stream_id: u32,
+        payload: Vec<u8>,
+    }
+
+    fn decode_frames(mut bytes: &[u8]) -> Vec<Frame> {
+        let mut frames = Vec::new();
+        while !bytes.is_empty() {
+            let length = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize;
+            let stream_id = u32::from_be_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]);
+            frames.push(Frame {
+                frame_type: bytes[3],
+                flags: bytes[4],
+                stream_id,
+                payload: bytes[9..9 + length].to_vec(),
+            });
+            bytes = &bytes[9 + length..];
+        }
+        frames
+    }
+    fn decode_integer(block: &mut &[u8]) -> usize {
+        let mut value = (block[0] & 0x7f) as usize;
+        *block = &block[1..];
+        if value < 0x7f {
+            return value;
+        }
+        let mut shift = 0;
+        loop {
+            let byte = block[0];
+            *block = &block[1..];
+            value += ((byte & 0x7f) as usize) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+    fn decode_block(mut block: &[u8]) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        while !block.is_empty() {
+            assert_eq!(block[0], 0x00);
+            block = &block[1..];
+            let mut field = Vec::new();
+            for _ in 0..2 {
+                let length = decode_integer(&mut block);
+                field.push(String::from_utf8(block[..length].to_vec()).unwrap());
+                block = &block[length..];
+            }
+            fields.push((field.remove(0), field.remove(0)));
+        }
+        fields
+    }
+
+    #[test]
+    fn parse_h2_request() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("https://example.com/upload?x=1")
+            .version(Version::HTTP_2)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body("hello")
+            .unwrap();
+        let bytes = HttpParser::from_request(&request).parse_h2().unwrap();
+        let frames = decode_frames(&bytes);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame_type, 0x1);
+        assert_eq!(frames[0].flags, 0x4);
+        assert_eq!(frames[0].stream_id, 1);
+        assert_eq!(
+            decode_block(&frames[0].payload),
+            vec![
+                (String::from(":method"), String::from("POST")),
+                (String::from(":scheme"), String::from("https")),
+                (String::from(":authority"), String::from("example.com")),
+                (String::from(":path"), String::from("/upload?x=1")),
+                (String::from("content-type"), String::from("text/plain")),
+            ]
+        );
+        assert_eq!(frames[1].frame_type, 0x0);
+        assert_eq!(frames[1].flags, 0x1);
+        assert_eq!(frames[1].payload, b"hello");
+    }
+    #[test]
+    fn parse_h2_response_without_body() {
+        let response = Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .version(Version::HTTP_2)
+            .header("x-long", "v".repeat(200))
+            .body("")
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_h2().unwrap();
+        let frames = decode_frames(&bytes);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].flags, 0x1 | 0x4);
+        assert_eq!(
+            decode_block(&frames[0].payload),
+            vec![
+                (String::from(":status"), String::from("204")),
+                (String::from("x-long"), "v".repeat(200)),
+            ]
+        );
+    }
+    #[test]
+    fn parse_h2_bodiless_statuses_end_with_headers() {
+        for status in [StatusCode::CONTINUE, StatusCode::NOT_MODIFIED] {
+            let response = Response::builder()
+                .status(status)
+                .version(Version::HTTP_2)
+                .body("accidental")
+                .unwrap();
+            let frames = decode_frames(&HttpParser::from_response(&response).parse_h2().unwrap());
+            assert_eq!(frames.len(), 1);
+            assert_eq!(frames[0].frame_type, 0x1);
+            assert_eq!(frames[0].flags, 0x1 | 0x4);
+        }
+    }
+    #[test]
+    fn parse_h2_splits_large_bodies() {
+        let body = vec![7u8; 40000];
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_2)
+            .body(body.clone())
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_h2().unwrap();
+        let frames = decode_frames(&bytes);
+        let data = frames.iter().filter(|frame| frame.frame_type == 0x0).collect::<Vec<_>>();
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.iter().map(|frame| frame.flags).collect::<Vec<_>>(), vec![0, 0, 0x1]);
+        assert_eq!(data.iter().flat_map(|frame| frame.payload.clone()).collect::<Vec<_>>(), body);
+    }
+    #[test]
+    fn parse_h2_rejects_other_versions() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_h2().unwrap_err(),
+            ParseError::UnsupportedVersion(Version::HTTP_11)
+        );
+    }
+    #[test]
+    fn parse_h2_debug_lists_pseudo_headers_first() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("https://example.com/search?q=rust")
+            .version(Version::HTTP_2)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body("hi")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_h2_debug().unwrap(),
+            ":method: POST\n:scheme: https\n:authority: example.com\n:path: /search?q=rust\ncontent-type: text/plain\n\nhi"
+        );
+
+        let response = Response::builder().status(StatusCode::OK).version(Version::HTTP_11).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_h2_debug().unwrap_err(),
+            ParseError::UnsupportedVersion(Version::HTTP_11)
+        );
+    }
+    #[test]
+    fn parse_h2_drops_connection_specific_headers() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("https://example.com/")
+            .version(Version::HTTP_2)
+            .header(header::CONNECTION, "keep-alive")
+            .header("keep-alive", "timeout=5")
+            .header("proxy-connection", "keep-alive")
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .header(header::UPGRADE, "websocket")
+            .header(header::TE, "gzip, trailers")
+            .header(header::ACCEPT, "*/*")
+            .body("")
+            .unwrap();
+        let bytes = HttpParser::from_request(&request).parse_h2().unwrap();
+        let fields = decode_block(&decode_frames(&bytes)[0].payload);
+        assert_eq!(
+            fields[4..],
+            [
+                (String::from("te"), String::from("trailers")),
+                (String::from("accept"), String::from("*/*")),
+            ]
+        );
+
+        let mut request = request;
+        request.headers_mut().insert(header::TE, "gzip".parse().unwrap());
+        let dump = HttpParser::from_request(&request).parse_h2_debug().unwrap();
+        assert_eq!(dump, ":method: GET\n:scheme: https\n:authority: example.com\n:path: /\naccept: */*\n\n");
+    }
+    #[test]
+    fn parse_h2_connect_has_only_method_and_authority() {
+        let request = Request::builder()
+            .method(Method::CONNECT)
+            .uri("example.com:443")
+            .version(Version::HTTP_2)
+            .body("")
+            .unwrap();
+        let bytes = HttpParser::from_request(&request).parse_h2().unwrap();
+        assert_eq!(
+            decode_block(&decode_frames(&bytes)[0].payload),
+            vec![
+                (String::from(":method"), String::from("CONNECT")),
+                (String::from(":authority"), String::from("example.com:443")),
+            ]
+        );
+    }
+}
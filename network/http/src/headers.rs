@@ -0,0 +1,663 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderMap, HeaderName, HeaderValue, Request, Version};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::net::IpAddr;
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes a request with its `Host` header first, as RFC 7230 recommends,
+    /// keeping the relative order of every other header.
+    pub fn parse_request_host_first(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let mut headers = HeaderMap::with_capacity(request.headers().len());
+        for value in request.headers().get_all(header::HOST) {
+            headers.append(header::HOST, value.clone());
+        }
+        for (name, value) in request.headers() {
+            if name != header::HOST {
+                headers.append(name, value.clone());
+            }
+        }
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes a request with all `Cookie` headers joined into one `Cookie: a=1; b=2` line
+    /// per RFC 6265. `Set-Cookie` is a response header and is never merged.
+    pub fn parse_request_merged_cookies(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let cookies = request.headers()
+            .get_all(header::COOKIE)
+            .iter()
+            .map(HeaderValue::as_bytes)
+            .collect::<Vec<_>>()
+            .join(&b"; "[..]);
+        let mut headers = HeaderMap::with_capacity(request.headers().len());
+        for (name, value) in request.headers() {
+            if name != header::COOKIE {
+                headers.append(name, value.clone());
+            } else if !headers.contains_key(header::COOKIE) {
+                let cookies = HeaderValue::from_bytes(&cookies)
+                    .map_err(|_| ParseError::InvalidHeaderValue(header::COOKIE.to_string()))?;
+                headers.insert(header::COOKIE, cookies);
+            }
+        }
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes with repeated list-valued headers joined by `, ` into one line (RFC 7230 §3.2.2).
+    /// `Set-Cookie`, `Cookie` and the authentication challenges keep one line per value.
+    pub fn parse_combined(&self) -> Result<String, ParseError> {
+        let mut headers = HeaderMap::with_capacity(self.headers().keys_len());
+        for name in self.headers().keys() {
+            let values = self.headers().get_all(name);
+            if NON_COMBINABLE.contains(name) || values.iter().nth(1).is_none() {
+                for value in values {
+                    headers.append(name, value.clone());
+                }
+                continue;
+            }
+            let combined = values.iter()
+                .map(HeaderValue::as_bytes)
+                .collect::<Vec<_>>()
+                .join(&b", "[..]);
+            let combined = HeaderValue::from_bytes(&combined)
+                .map_err(|_| ParseError::InvalidHeaderValue(name.to_string()))?;
+            headers.insert(name, combined);
+        }
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes for forwarding by a proxy: drops the hop-by-hop headers of RFC 7230 §6.1
+    /// plus every header the `Connection` header names.
+    pub fn parse_forwarded(&self) -> Result<String, ParseError> {
+        let connection_named = self.headers()
+            .get_all(header::CONNECTION)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|token| HeaderName::from_bytes(token.trim().as_bytes()).ok())
+            .collect::<Vec<_>>();
+        let mut headers = HeaderMap::with_capacity(self.headers().len());
+        for (name, value) in self.headers() {
+            if !HOP_BY_HOP.contains(name) && !connection_named.contains(name) {
+                headers.append(name, value.clone());
+            }
+        }
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes with headers sorted by name for reproducible snapshots. Repeated headers keep
+    /// their relative order.
+    pub fn parse_sorted_headers(&self) -> Result<String, ParseError> {
+        let mut names = self.headers().keys().collect::<Vec<_>>();
+        names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        let mut headers = HeaderMap::with_capacity(self.headers().len());
+        for name in names {
+            for value in self.headers().get_all(name) {
+                headers.append(name, value.clone());
+            }
+        }
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes with `Server` added to a response or `User-Agent` to a request, from `server`
+    /// or `user_agent` respectively, unless the message already has one.
+    pub fn parse_with_agent(&self, server: Option<&str>, user_agent: Option<&str>) -> Result<String, ParseError> {
+        let (name, agent) = match &self.message {
+            Message::Request(_) => (header::USER_AGENT, user_agent),
+            Message::Response(_) => (header::SERVER, server),
+        };
+        let Some(agent) = agent.filter(|_| !self.headers().contains_key(&name)) else {
+            return self.parse();
+        };
+        let value = HeaderValue::from_str(agent).map_err(|_| ParseError::InvalidHeaderValue(name.to_string()))?;
+        let mut headers = self.headers().clone();
+        headers.insert(name, value);
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes with header lines longer than `max_line_width` folded onto obs-fold continuation
+    /// lines (RFC 7230 §3.2.4), for testing tolerant parsers. Values only break at a single space,
+    /// which the fold replaces, so unfolding restores them; a value with no such space stays long.
+    pub fn parse_folded(&self, max_line_width: usize) -> Result<String, ParseError> {
+        let eol = self.line_ending.as_str();
+        if self.version() == Version::HTTP_09 {
+            return self.parse();
+        }
+        let mut message = self.parse_start_line()?;
+        message.push_str(eol);
+        for (name, value) in self.headers() {
+            let name = self.header_name(name);
+            let value = String::from_utf8_lossy(value.as_bytes());
+            let mut rest = value.as_ref();
+            let mut budget = max_line_width.saturating_sub(name.len() + 1);
+            message.push_str(&name);
+            message.push(':');
+            while let Some(split) = fold_point(rest, budget) {
+                message.push_str(&rest[..split]);
+                message.push_str(eol);
+                message.push(' ');
+                rest = &rest[split + 1..];
+                budget = max_line_width.saturating_sub(1);
+            }
+            message.push_str(rest);
+            message.push_str(eol);
+        }
+        message.push_str(eol);
+        message.push_str(&self.body_string());
+        Ok(message)
+    }
+    /// Serializes with header names in Title-Case (`Content-Type`, `ETag`, `WWW-Authenticate`)
+    /// for peers that are picky about casing. `parse()` keeps the lowercase names of `HeaderMap`.
+    pub fn parse_title_case(&self) -> Result<String, ParseError> {
+        let mut parser = self.borrowed();
+        parser.title_case = true;
+        parser.parse()
+    }
+    /// Serializes with the version's default `Connection` header made explicit: `close` for
+    /// HTTP/1.0, `keep-alive` for HTTP/1.1. An existing `Connection` header is left alone.
+    pub fn parse_with_connection_defaults(&self) -> Result<String, ParseError> {
+        let default = match self.version() {
+            Version::HTTP_10 => "close",
+            Version::HTTP_11 => "keep-alive",
+            _ => return self.parse(),
+        };
+        if self.headers().contains_key(header::CONNECTION) {
+            return self.parse();
+        }
+        let mut headers = self.headers().clone();
+        headers.insert(header::CONNECTION, HeaderValue::from_static(default));
+        self.parse_with_headers(&headers)
+    }
+    /// Serializes with `Connection: close`, replacing any other `Connection` value and dropping
+    /// `Keep-Alive`, so a reader can take the end of the connection as the end of the message.
+    pub fn parse_until_close(&self) -> Result<String, ParseError> {
+        let mut headers = self.headers().clone();
+        headers.remove("keep-alive");
+        headers.insert(header::CONNECTION, HeaderValue::from_static("close"));
+        self.parse_with_headers(&headers)
+    }
+}
+/// Header totals as `HttpParser::header_stats()` computes them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeaderStats {
+    /// Header lines, counting each value of a repeated header.
+    pub count: usize,
+    /// Bytes the header lines take on the wire, line endings included.
+    pub bytes: usize,
+}
+impl<T> HttpParser<'_, T> {
+    /// Counts the header lines and their serialized size without building them.
+    pub fn header_stats(&self) -> HeaderStats {
+        let eol = self.line_ending.as_str().len();
+        self.headers().iter().fold(HeaderStats { count: 0, bytes: 0 }, |stats, (name, value)| HeaderStats {
+            count: stats.count + 1,
+            bytes: stats.bytes + name.as_str().len() + 1 + value.len() + eol,
+        })
+    }
+    /// The first value of `name`, whichever kind of message this is.
+    pub fn header(&self, name: &HeaderName) -> Option<&HeaderValue> {
+        self.headers().get(name)
+    }
+    pub fn has_header(&self, name: &HeaderName) -> bool {
+        self.headers().contains_key(name)
+    }
+}
+/// Adds the `Accept`, `Accept-Encoding`, `Accept-Language` and `User-Agent` headers a browser
+/// would send, keeping any the request already has.
+pub fn with_default_browser_headers<T>(mut request: Request<T>, user_agent: HeaderValue) -> Request<T> {
+    let headers = request.headers_mut();
+    let defaults = [
+        (header::ACCEPT, HeaderValue::from_static("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")),
+        (header::ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate, br")),
+        (header::ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5")),
+        (header::USER_AGENT, user_agent),
+    ];
+    for (name, value) in defaults {
+        headers.entry(name).or_insert(value);
+    }
+    request
+}
+/// Copies each header of `defaults` the request does not already have, with all its values;
+/// headers the caller set are left untouched.
+pub fn apply_defaults<T>(req: &mut Request<T>, defaults: &HeaderMap) {
+    let headers = req.headers_mut();
+    for name in defaults.keys() {
+        if !headers.contains_key(name) {
+            for value in defaults.get_all(name) {
+                headers.append(name, value.clone());
+            }
+        }
+    }
+}
+/// Appends `client_ip` to the RFC 7239 `Forwarded` chain, creating the header if needed, and
+/// to `X-Forwarded-For` when the request already carries one.
+pub fn append_forwarded<T>(req: &mut Request<T>, client_ip: IpAddr) -> Result<(), ParseError> {
+    let node = match client_ip {
+        IpAddr::V4(ip) => format!("for={ip}"),
+        IpAddr::V6(ip) => format!("for=\"[{ip}]\""),
+    };
+    let headers = req.headers_mut();
+    append_to_list(headers, header::FORWARDED, &node)?;
+    if headers.contains_key(X_FORWARDED_FOR) {
+        append_to_list(headers, X_FORWARDED_FOR, &client_ip.to_string())?;
+    }
+    Ok(())
+}
+/// Appends this intermediary to the `Via` chain as `{protocol} {pseudonym}`, creating the
+/// header if needed. A leading `HTTP/` is dropped from `received_protocol`, as RFC 7230 allows.
+/// A protocol or pseudonym that cannot go in a header value is an `InvalidHeaderValue` error.
+pub fn append_via<T>(msg: &mut Request<T>, received_protocol: &str, pseudonym: &str) -> Result<(), ParseError> {
+    let protocol = received_protocol.strip_prefix("HTTP/").unwrap_or(received_protocol);
+    append_to_list(msg.headers_mut(), header::VIA, &format!("{protocol} {pseudonym}"))
+}
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+/// Joins every existing value of `name` with `item` into a single comma-separated value. If that
+/// is not a valid header value the headers are left as they were.
+pub(crate) fn append_to_list(headers: &mut HeaderMap, name: HeaderName, item: &str) -> Result<(), ParseError> {
+    let mut list = headers.get_all(&name)
+        .iter()
+        .map(|value| value.as_bytes())
+        .collect::<Vec<_>>()
+        .join(&b", "[..]);
+    if !list.is_empty() {
+        list.extend_from_slice(b", ");
+    }
+    list.extend_from_slice(item.as_bytes());
+    let value = HeaderValue::from_bytes(&list).map_err(|_| ParseError::InvalidHeaderValue(name.to_string()))?;
+    headers.insert(name, value);
+    Ok(())
+}
+pub(crate) const HOP_BY_HOP: [HeaderName; 8] = [
+    header::CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    header::TRANSFER_ENCODING,
+    header::TE,
+    header::TRAILER,
+    header::UPGRADE,
+    header::PROXY_AUTHORIZATION,
+    header::PROXY_AUTHENTICATE,
+];
+/// Headers whose values may themselves contain commas, or that RFC 6265 forbids folding.
+const NON_COMBINABLE: [HeaderName; 4] = [
+    header::SET_COOKIE,
+    header::COOKIE,
+    header::WWW_AUTHENTICATE,
+    header::PROXY_AUTHENTICATE,
+];
+/// Title-Cases a lowercase header name, keeping the conventional spelling of irregular ones.
+pub(crate) fn title_case(name: &str) -> String {
+    name.split('-')
+        .map(|word| match word {
+            "etag" => String::from("ETag"),
+            "www" => String::from("WWW"),
+            "te" => String::from("TE"),
+            "dnt" => String::from("DNT"),
+            "md5" => String::from("MD5"),
+            "xss" => String::from("XSS"),
+            "websocket" => String::from("WebSocket"),
+            word => {
+                let mut chars = word.chars();
+                chars.next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+/// Where to break `value` so the piece before it fits `budget`: the last lone space in reach,
+/// or the first one if none is. `None` if the value already fits or has nowhere to break.
+fn fold_point(value: &str, budget: usize) -> Option<usize> {
+    if value.len() <= budget {
+        return None;
+    }
+    let bytes = value.as_bytes();
+    let mut spaces = (1..bytes.len() - 1)
+        .filter(|&index| bytes[index] == b' ' && bytes[index - 1] != b' ' && bytes[index + 1] != b' ');
+    let first = spaces.next()?;
+    let last_in_reach = core::iter::once(first).chain(spaces).take_while(|&index| index <= budget).last();
+    Some(last_in_reach.unwrap_or(first))
+}
+/// RFC 7230 `token`: one or more tchars, i.e. visible ASCII other than delimiters.
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+#[cfg(test)]
+mod tests {
+    use super::title_case;
+    use crate::{append_forwarded, append_via, apply_defaults, with_default_browser_headers, HeaderStats, HttpParser, ParseError};
+    use http::{header, HeaderMap, HeaderValue, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_request_host_first_moves_host() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::ACCEPT, "*/*")
+            .header(header::USER_AGENT, "test")
+            .header(header::HOST, "localhost")
+            .body("")
+            .unwrap();
+        let request = HttpParser::from_request(&request).parse_request_host_first().unwrap();
+        assert_eq!(request, "GET / HTTP/1.1\r\nhost:localhost\r\naccept:*/*\r\nuser-agent:test\r\n\r\n");
+    }
+    #[test]
+    fn parse_request_merged_cookies_joins_values() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .header(header::COOKIE, "a=1")
+            .header(header::COOKIE, "b=2")
+            .header(header::COOKIE, "c=3")
+            .header(header::SET_COOKIE, "x=1")
+            .header(header::SET_COOKIE, "y=2")
+            .body("")
+            .unwrap();
+        let request = HttpParser::from_request(&request).parse_request_merged_cookies().unwrap();
+        assert_eq!(
+            request,
+            "GET / HTTP/1.1\r\nhost:localhost\r\ncookie:a=1; b=2; c=3\r\nset-cookie:x=1\r\nset-cookie:y=2\r\n\r\n"
+        );
+        assert_eq!(request.matches("cookie:a").count(), 1);
+    }
+    #[test]
+    fn parse_request_host_first_rejects_responses() {
+        let response = Response::builder().body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_request_host_first().unwrap_err(),
+            ParseError::WrongVariant
+        );
+    }
+    #[test]
+    fn title_case_handles_irregular_names() {
+        assert_eq!(title_case("content-type"), "Content-Type");
+        assert_eq!(title_case("x-forwarded-for"), "X-Forwarded-For");
+        assert_eq!(title_case("etag"), "ETag");
+        assert_eq!(title_case("www-authenticate"), "WWW-Authenticate");
+        assert_eq!(title_case("sec-websocket-key"), "Sec-WebSocket-Key");
+        assert_eq!(title_case("te"), "TE");
+    }
+    #[test]
+    fn parse_title_case_rewrites_names() {
+        let response = Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_TYPE, "text/html")
+            .header(header::ETAG, "\"abc\"")
+            .header(header::WWW_AUTHENTICATE, "Basic")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(
+            parser.parse_title_case().unwrap(),
+            "HTTP/1.1 401 Unauthorized\r\nContent-Type:text/html\r\nETag:\"abc\"\r\nWWW-Authenticate:Basic\r\n\r\n"
+        );
+        assert!(parser.parse().unwrap().contains("content-type:text/html"));
+    }
+    #[test]
+    fn parse_with_connection_defaults() {
+        let response = Response::builder().status(StatusCode::OK).version(Version::HTTP_10).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_with_connection_defaults().unwrap(),
+            "HTTP/1.0 200 OK\r\nconnection:close\r\n\r\n"
+        );
+
+        let request = Request::builder().uri("/").version(Version::HTTP_11).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_with_connection_defaults().unwrap(),
+            "GET / HTTP/1.1\r\nconnection:keep-alive\r\n\r\n"
+        );
+
+        let request = Request::builder()
+            .uri("/")
+            .version(Version::HTTP_10)
+            .header(header::CONNECTION, "keep-alive")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_with_connection_defaults().unwrap(),
+            "GET / HTTP/1.0\r\nconnection:keep-alive\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_until_close_forces_close() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONNECTION, "keep-alive")
+            .header("keep-alive", "timeout=5")
+            .body("done")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_until_close().unwrap(),
+            "HTTP/1.1 200 OK\r\nconnection:close\r\n\r\ndone"
+        );
+
+        let request = Request::builder().uri("/").version(Version::HTTP_10).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_until_close().unwrap(),
+            "GET / HTTP/1.0\r\nconnection:close\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_combined_merges_list_headers() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .header(header::SET_COOKIE, "a=1")
+            .header(header::CACHE_CONTROL, "no-store")
+            .header(header::SET_COOKIE, "b=2")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_combined().unwrap(),
+            "HTTP/1.1 200 OK\r\ncache-control:no-cache, no-store\r\nset-cookie:a=1\r\nset-cookie:b=2\r\n\r\n"
+        );
+
+        let request = Request::builder()
+            .uri("/")
+            .header(header::ACCEPT, "text/html")
+            .header(header::ACCEPT, "application/json")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_combined().unwrap(),
+            "GET / HTTP/1.1\r\naccept:text/html, application/json\r\n\r\n"
+        );
+    }
+    #[test]
+    fn with_default_browser_headers_fills_missing() {
+        let request = Request::builder()
+            .uri("http://example.com/")
+            .header(header::ACCEPT_LANGUAGE, "de-DE")
+            .body("")
+            .unwrap();
+        let request = with_default_browser_headers(request, HeaderValue::from_static("crawler/1.0"));
+        assert_eq!(request.headers()[header::ACCEPT_LANGUAGE], "de-DE");
+        assert_eq!(request.headers().get_all(header::ACCEPT_LANGUAGE).iter().count(), 1);
+        assert_eq!(request.headers()[header::ACCEPT_ENCODING], "gzip, deflate, br");
+        assert_eq!(request.headers()[header::USER_AGENT], "crawler/1.0");
+        assert!(request.headers().contains_key(header::ACCEPT));
+        assert!(HttpParser::from_request(&request).parse().unwrap().contains("\r\nuser-agent:crawler/1.0\r\n"));
+    }
+    #[test]
+    fn apply_defaults_keeps_caller_headers() {
+        let mut defaults = HeaderMap::new();
+        defaults.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+        defaults.append(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        defaults.append(header::CACHE_CONTROL, HeaderValue::from_static("no-transform"));
+
+        let mut request = Request::builder().uri("/").body("").unwrap();
+        apply_defaults(&mut request, &defaults);
+        assert_eq!(request.headers()[header::ACCEPT], "application/json");
+        assert_eq!(request.headers().get_all(header::CACHE_CONTROL).iter().count(), 2);
+
+        let mut request = Request::builder().uri("/").header(header::ACCEPT, "text/html").body("").unwrap();
+        apply_defaults(&mut request, &defaults);
+        assert_eq!(request.headers().get_all(header::ACCEPT).iter().collect::<Vec<_>>(), ["text/html"]);
+        assert_eq!(
+            HttpParser::from_request(&request).parse().unwrap(),
+            "GET / HTTP/1.1\r\naccept:text/html\r\ncache-control:no-cache\r\ncache-control:no-transform\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_forwarded_strips_hop_by_hop() {
+        let request = Request::builder()
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "origin.example")
+            .header(header::CONNECTION, "close, X-Custom")
+            .header("x-custom", "1")
+            .header("keep-alive", "timeout=5")
+            .header(header::PROXY_AUTHORIZATION, "Basic Zm9vOmJhcg==")
+            .header(header::ACCEPT, "*/*")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_forwarded().unwrap(),
+            "GET / HTTP/1.1\r\nhost:origin.example\r\naccept:*/*\r\n\r\n"
+        );
+    }
+    #[test]
+    fn header_lookup_on_both_variants() {
+        let request = Request::builder()
+            .uri("/")
+            .header(header::HOST, "example.com")
+            .header(header::ACCEPT, "text/html")
+            .header(header::ACCEPT, "text/plain")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.header(&header::HOST).unwrap(), "example.com");
+        assert_eq!(parser.header(&header::ACCEPT).unwrap(), "text/html");
+        assert!(parser.has_header(&header::ACCEPT));
+        assert!(!parser.has_header(&header::COOKIE));
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.header(&header::CONTENT_TYPE).unwrap(), "text/plain");
+        assert_eq!(parser.header(&header::HOST), None);
+        assert!(!parser.has_header(&header::HOST));
+    }
+    #[test]
+    fn append_forwarded_extends_chain() {
+        let mut request = Request::builder().uri("/").body("").unwrap();
+        append_forwarded(&mut request, "192.0.2.60".parse().unwrap()).unwrap();
+        assert_eq!(request.headers()[header::FORWARDED], "for=192.0.2.60");
+        assert!(!request.headers().contains_key("x-forwarded-for"));
+
+        let mut request = Request::builder()
+            .uri("/")
+            .header(header::FORWARDED, "for=192.0.2.43")
+            .header(header::FORWARDED, "for=198.51.100.17;proto=https")
+            .header("x-forwarded-for", "192.0.2.43, 198.51.100.17")
+            .body("")
+            .unwrap();
+        append_forwarded(&mut request, "2001:db8:cafe::17".parse().unwrap()).unwrap();
+        assert_eq!(
+            request.headers()[header::FORWARDED],
+            "for=192.0.2.43, for=198.51.100.17;proto=https, for=\"[2001:db8:cafe::17]\""
+        );
+        assert_eq!(request.headers()["x-forwarded-for"], "192.0.2.43, 198.51.100.17, 2001:db8:cafe::17");
+        assert_eq!(request.headers().get_all(header::FORWARDED).iter().count(), 1);
+    }
+    #[test]
+    fn append_via_extends_chain() {
+        let mut request = Request::builder().uri("/").body("").unwrap();
+        append_via(&mut request, "HTTP/1.1", "proxy-a").unwrap();
+        assert_eq!(request.headers()[header::VIA], "1.1 proxy-a");
+        append_via(&mut request, "1.0", "fred").unwrap();
+        assert_eq!(request.headers()[header::VIA], "1.1 proxy-a, 1.0 fred");
+        append_via(&mut request, "HTTP/2", "edge").unwrap();
+        assert_eq!(request.headers()[header::VIA], "1.1 proxy-a, 1.0 fred, 2 edge");
+        assert_eq!(
+            append_via(&mut request, "1.1", "bad\r\nx-injected: 1").unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("via"))
+        );
+        assert_eq!(request.headers()[header::VIA], "1.1 proxy-a, 1.0 fred, 2 edge");
+    }
+    #[test]
+    fn parse_sorted_headers_ignores_insertion_order() {
+        let build = |headers: &[(&str, &str)]| {
+            let mut builder = Response::builder().status(StatusCode::OK);
+            for (name, value) in headers {
+                builder = builder.header(*name, *value);
+            }
+            builder.body("").unwrap()
+        };
+        let a = build(&[("x-b", "2"), ("set-cookie", "a=1"), ("content-type", "text/plain"), ("set-cookie", "b=2")]);
+        let b = build(&[("content-type", "text/plain"), ("set-cookie", "a=1"), ("set-cookie", "b=2"), ("x-b", "2")]);
+        let sorted = HttpParser::from_response(&a).parse_sorted_headers().unwrap();
+        assert_eq!(
+            sorted,
+            "HTTP/1.1 200 OK\r\ncontent-type:text/plain\r\nset-cookie:a=1\r\nset-cookie:b=2\r\nx-b:2\r\n\r\n"
+        );
+        assert_eq!(HttpParser::from_response(&b).parse_sorted_headers().unwrap(), sorted);
+    }
+    #[test]
+    fn header_stats_counts_lines_and_bytes() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .header(header::SET_COOKIE, "a=1")
+            .header(header::SET_COOKIE, "b=2")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let stats = parser.header_stats();
+        assert_eq!(stats, HeaderStats { count: 3, bytes: 25 + 16 + 16 });
+        assert_eq!(stats.bytes, parser.parse_header_bytes().len());
+    }
+    #[test]
+    fn parse_with_agent_injects_missing_agents() {
+        let response = Response::builder().status(StatusCode::OK).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_with_agent(Some("gateway/1.0"), Some("ignored")).unwrap(),
+            "HTTP/1.1 200 OK\r\nserver:gateway/1.0\r\n\r\n"
+        );
+        let request = Request::builder().uri("/").header(header::HOST, "x").body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_with_agent(Some("ignored"), Some("client/2.0")).unwrap(),
+            "GET / HTTP/1.1\r\nhost:x\r\nuser-agent:client/2.0\r\n\r\n"
+        );
+
+        let request = Request::builder().uri("/").header(header::USER_AGENT, "curl/8.0").body("").unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.parse_with_agent(None, Some("client/2.0")).unwrap(), parser.parse().unwrap());
+        assert_eq!(
+            HttpParser::from_response(&response).parse_with_agent(Some("bad\nagent"), None).unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("server"))
+        );
+    }
+    #[test]
+    fn parse_folded_wraps_long_values() {
+        let value = "text/html, application/xhtml+xml, application/xml;q=0.9, */*;q=0.8";
+        let request = Request::builder()
+            .uri("/")
+            .header(header::HOST, "example.com")
+            .header(header::ACCEPT, value)
+            .body("")
+            .unwrap();
+        let folded = HttpParser::from_request(&request).parse_folded(30).unwrap();
+        assert_eq!(
+            folded,
+            "GET / HTTP/1.1\r\nhost:example.com\r\naccept:text/html,\r\n application/xhtml+xml,\r\n \
+             application/xml;q=0.9,\r\n */*;q=0.8\r\n\r\n"
+        );
+        assert!(folded.split("\r\n").all(|line| line.len() <= 30));
+        let unfolded = HttpParser::parse_request_str(&folded).unwrap();
+        assert_eq!(unfolded.headers()[header::ACCEPT], value);
+
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.parse_folded(200).unwrap(), parser.parse().unwrap());
+    }
+}
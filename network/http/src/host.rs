@@ -0,0 +1,63 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes a request with a Unicode `Host` header converted to its A-label (`xn--`) form.
+    /// ASCII hosts are left untouched. `http::Uri` only holds ASCII authorities, so the URI
+    /// never needs converting.
+    pub fn parse_request_idna(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let Some(host) = request.headers().get(header::HOST).filter(|host| !host.as_bytes().is_ascii()) else {
+            return self.parse();
+        };
+        let invalid = || ParseError::InvalidHeaderValue(header::HOST.to_string());
+        let host = core::str::from_utf8(host.as_bytes()).map_err(|_| invalid())?;
+        let (name, port) = match host.rsplit_once(':') {
+            Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit()) => (name, Some(port)),
+            _ => (host, None),
+        };
+        let mut ascii = idna::domain_to_ascii(name).map_err(|_| invalid())?;
+        if let Some(port) = port {
+            ascii = format!("{ascii}:{port}");
+        }
+        let mut headers = request.headers().clone();
+        headers.insert(header::HOST, HeaderValue::from_str(&ascii).map_err(|_| invalid())?);
+        self.parse_with_headers(&headers)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, HeaderValue, Request, Response};
+
+    #[test]
+    fn parse_request_idna_converts_unicode_host() {
+        let request = Request::builder()
+            .uri("/index.html")
+            .header(header::HOST, HeaderValue::from_bytes("例え.テスト:8080".as_bytes()).unwrap())
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_request_idna().unwrap(),
+            "GET /index.html HTTP/1.1\r\nhost:xn--r8jz45g.xn--zckzah:8080\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_request_idna_leaves_ascii_hosts() {
+        let request = Request::builder()
+            .uri("http://xn--r8jz45g.xn--zckzah/")
+            .header(header::HOST, "Example.COM")
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.parse_request_idna().unwrap(), parser.parse().unwrap());
+
+        let response = Response::builder().body("").unwrap();
+        assert_eq!(HttpParser::from_response(&response).parse_request_idna().unwrap_err(), ParseError::WrongVariant);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{chunked, decode, FramingMode, HttpMessage, ParseError};
+use http::header;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// Buffers bytes from a socket and yields each message once its head and body have arrived.
+/// A response framed by connection close never completes here, since no length is known.
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalParser {
+    buf: Vec<u8>,
+}
+impl IncrementalParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+    /// The next complete message, or `None` if more bytes are needed. Bytes after the message
+    /// stay buffered for the next call.
+    pub fn try_take(&mut self) -> Result<Option<HttpMessage>, ParseError> {
+        let Some(head_end) = self.buf.windows(4).position(|window| window == b"\r\n\r\n") else {
+            return Ok(None);
+        };
+        let (head, _) = decode::split_head(&self.buf)?;
+        let mut message = if head.starts_with("HTTP/") {
+            HttpMessage::Response(decode::parse_response_head(head)?.map(|_| Vec::new()))
+        } else {
+            HttpMessage::Request(decode::parse_request_head(head)?.map(|_| Vec::new()))
+        };
+        let body = &self.buf[head_end + 4..];
+        let (payload, body_len) = match message.parser().framing_mode() {
+            FramingMode::ContentLength(length) => {
+                let length = usize::try_from(length)
+                    .map_err(|_| ParseError::InvalidHeaderValue(header::CONTENT_LENGTH.to_string()))?;
+                let Some(payload) = body.get(..length) else {
+                    return Ok(None);
+                };
+                (payload.to_vec(), length)
+            }
+            FramingMode::Chunked => {
+                let Some((payload, consumed)) = chunked::decode_chunked_prefix(body)? else {
+                    return Ok(None);
+                };
+                chunked::unchunk_headers(message.headers_mut(), payload.len());
+                (payload, consumed)
+            }
+            FramingMode::UntilClose => return Ok(None),
+        };
+        match &mut message {
+            HttpMessage::Request(request) => *request.body_mut() = payload,
+            HttpMessage::Response(response) => *response.body_mut() = payload,
+        }
+        self.buf.drain(..head_end + 4 + body_len);
+        Ok(Some(message))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpMessage, IncrementalParser};
+
+    #[test]
+    fn incremental_parser_waits_for_body() {
+        let mut parser = IncrementalParser::new();
+        parser.feed(b"POST /upload HTTP/1.1\r\nhost:x\r\ncont");
+        assert!(parser.try_take().unwrap().is_none());
+        parser.feed(b"ent-length:11\r\n\r\nhello");
+        assert!(parser.try_take().unwrap().is_none());
+        parser.feed(b" world");
+        let HttpMessage::Request(request) = parser.try_take().unwrap().unwrap() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.uri(), "/upload");
+        assert_eq!(request.body(), b"hello world");
+        assert!(parser.try_take().unwrap().is_none());
+    }
+    #[test]
+    fn incremental_parser_splits_pipelined_messages() {
+        let mut parser = IncrementalParser::new();
+        parser.feed(b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\n\r\n2\r\nhi\r\n0\r\n\r\n");
+        parser.feed(b"HTTP/1.1 304 Not Modified\r\ncontent-length:42\r\n\r\nHTTP/1.1 200 OK\r\n\r\n");
+        let HttpMessage::Response(first) = parser.try_take().unwrap().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(first.body(), b"hi");
+        let HttpMessage::Response(second) = parser.try_take().unwrap().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(second.status(), 304);
+        assert!(second.body().is_empty());
+        // No framing headers: the body runs until the connection closes.
+        assert!(parser.try_take().unwrap().is_none());
+
+        parser = IncrementalParser::new();
+        parser.feed(b"GET / HTTP/1.1\r\nbad header\r\n\r\n");
+        assert!(parser.try_take().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::HttpParser;
+use std::io;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// `write_to` for async writers: the head, then the body, each with `write_all`.
+    pub async fn write_to_async<W: AsyncWrite + Unpin>(&self, w: &mut W) -> io::Result<()> {
+        let head = self.head_bytes()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        w.write_all(&head).await?;
+        w.write_all(self.body_bytes()).await
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::HttpParser;
+    use http::{header, Response, StatusCode, Version};
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn write_to_async_matches_parse() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "5")
+            .body("hello")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        parser.write_to_async(&mut server).await.unwrap();
+        drop(server);
+        let mut received = String::new();
+        client.read_to_string(&mut received).await.unwrap();
+        assert_eq!(received, parser.parse().unwrap());
+    }
+}
@@ -1,91 +1,640 @@
+// Alloc-only is the goal for builds without `std`, but it is not supported yet: the `http`
+// dependency always links std, so this crate cannot build for a real `no_std` target.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+extern crate http_crate as http;
+
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod auth;
+mod body;
+mod builder;
+mod cache;
+mod cache_control;
+mod canonical;
+mod capture;
+mod chunked;
+#[cfg(feature = "compression")]
+mod compression;
+mod conditional;
+mod content_disposition;
+mod content_type;
+mod cookie;
+mod cors;
+#[cfg(feature = "std")]
+mod date;
+mod debug;
+mod decode;
+mod diff;
+mod downgrade;
+mod early_hints;
+#[cfg(feature = "bytes")]
+mod encode;
+mod error;
+mod expect;
+mod ext_value;
+mod framing;
+mod h2;
+mod headers;
+#[cfg(feature = "idna")]
+mod host;
+mod incremental;
+#[cfg(feature = "tokio")]
+mod io_async;
+mod message;
+mod method_override;
+mod multipart;
+mod pipeline;
+mod range;
+mod raw;
+mod redirect;
+#[cfg(feature = "serde")]
+mod serialization;
+mod slice;
+mod sse;
+mod status_class;
+mod te;
+mod template;
+mod uri_form;
+mod validate;
+mod websocket;
+
+#[cfg(feature = "proptest")]
+pub use arbitrary::{arb_message, assert_roundtrip};
+pub use auth::{basic_auth, bearer_auth};
+pub use body::{map_body, map_response_body};
+pub use builder::MessageBuilder;
+pub use cache_control::CacheControl;
+pub use canonical::CanonicalOverrides;
+pub use capture::decode_framed;
+pub use conditional::conditional_get;
+pub use content_disposition::{content_disposition, Disposition};
+pub use content_type::{ContentType, MediaType};
+pub use cookie::{Cookie, SameSite};
+pub use cors::cors_preflight_response;
+pub use debug::DEFAULT_REDACTED_HEADERS;
+pub use decode::response_from_head;
+pub use diff::message_diff;
+pub use early_hints::serialize_with_early_hints;
+pub use error::ParseError;
+#[cfg(feature = "std")]
+pub use expect::write_continue_then_final;
+pub use ext_value::{decode_ext_value, encode_ext_value};
+pub use framing::{BodyLength, FramingMode};
+pub use headers::{append_forwarded, append_via, apply_defaults, with_default_browser_headers, HeaderStats};
+pub use incremental::IncrementalParser;
+pub use message::HttpMessage;
+pub use multipart::multipart_body;
+pub use pipeline::{serialize_all, serialize_pipeline};
+pub use range::partial_content_response;
+pub use raw::RawMessage;
+pub use redirect::redirect_response;
+pub use sse::{sse_body, SseEvent};
+pub use status_class::StatusClass;
+pub use te::te_header;
+pub use template::ResponseTemplate;
+pub use uri_form::UriForm;
+pub use websocket::{switching_protocols_response, websocket_accept_key, websocket_upgrade_request};
 pub use http::*;
-use std::fmt::Display;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+/// The token written on the start line for `version`, or `None` for versions this crate can't emit.
+pub fn version_str(version: Version) -> Option<&'static str> {
+    match version {
+        Version::HTTP_09 => Some("HTTP/0.9"),
+        Version::HTTP_10 => Some("HTTP/1.0"),
+        Version::HTTP_11 => Some("HTTP/1.1"),
+        Version::HTTP_2 => Some("HTTP/2"),
+        Version::HTTP_3 => Some("HTTP/3"),
+        _ => None,
+    }
+}
+/// The reason phrase written after a status code: the `http` crate's canonical reason, plus
+/// "Early Hints" for 103 (RFC 8297), which `http` does not name. Unknown codes get none.
+fn reason_phrase(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        103 => "Early Hints",
+        _ => status.canonical_reason().unwrap_or_default(),
+    }
+}
+/// The inverse of `version_str`: the `Version` for an exact token such as `HTTP/1.1`.
+pub fn version_from_str(token: &str) -> Option<Version> {
+    match token {
+        "HTTP/0.9" => Some(Version::HTTP_09),
+        "HTTP/1.0" => Some(Version::HTTP_10),
+        "HTTP/1.1" => Some(Version::HTTP_11),
+        "HTTP/2" => Some(Version::HTTP_2),
+        "HTTP/3" => Some(Version::HTTP_3),
+        _ => None,
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    CrLf,
+    Lf,
+}
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Lf => "\n",
+        }
+    }
+}
+/// A request or response to serialize. This used to be a `Copy` enum with public `Request` and
+/// `Response` variants; match on `as_request()` / `as_response()` instead.
+#[derive(Clone, Debug)]
+pub struct HttpParser<'a, T> {
+    message: Message<'a, T>,
+    line_ending: LineEnding,
+    title_case: bool,
+}
+#[derive(Clone, Debug)]
+enum Message<'a, T> {
+    Request(Source<'a, Request<T>>),
+    Response(Source<'a, Response<T>>),
+}
+#[derive(Clone, Debug)]
+enum Source<'a, M> {
+    Borrowed(&'a M),
+    Owned(M),
+}
+impl<M> Deref for Source<'_, M> {
+    type Target = M;
 
-#[derive(Clone, Copy, Debug)]
-pub enum HttpParser<'a, T: Display> {
-    Request(&'a Request<T>),
-    Response(&'a Response<T>),
+    fn deref(&self) -> &M {
+        match self {
+            Source::Borrowed(message) => message,
+            Source::Owned(message) => message,
+        }
+    }
 }
-impl<'a, T: Display> HttpParser<'a, T> {
+impl<'a, T> HttpParser<'a, T> {
+    fn new(message: Message<'a, T>) -> Self {
+        HttpParser {
+            message,
+            line_ending: LineEnding::default(),
+            title_case: false,
+        }
+    }
     pub fn from_request(request: &'a Request<T>) -> Self {
-        HttpParser::Request(request)
+        HttpParser::new(Message::Request(Source::Borrowed(request)))
     }
     pub fn from_response(response: &'a Response<T>) -> Self {
-        HttpParser::Response(response)
+        HttpParser::new(Message::Response(Source::Borrowed(response)))
+    }
+    pub fn from_owned_request(request: Request<T>) -> Self {
+        HttpParser::new(Message::Request(Source::Owned(request)))
+    }
+    pub fn from_owned_response(response: Response<T>) -> Self {
+        HttpParser::new(Message::Response(Source::Owned(response)))
+    }
+    pub fn with_line_ending(self, ending: LineEnding) -> Self {
+        HttpParser {
+            line_ending: ending,
+            ..self
+        }
     }
 }
-impl<T: Display> HttpParser<'_, T> {
-    fn parse_version(&self) -> Option<String> {
-        let version = match self {
-            HttpParser::Request(request) => {
+impl<'a, T> HttpParser<'a, &'a T> {
+    /// Serializes a request held as `Parts` plus a body, exactly as `from_request` would.
+    /// The parts are cloned into an owned request; the body stays borrowed.
+    pub fn from_request_parts(parts: &http::request::Parts, body: &'a T) -> Self {
+        HttpParser::from_owned_request(Request::from_parts(parts.clone(), body))
+    }
+    pub fn from_response_parts(parts: &http::response::Parts, body: &'a T) -> Self {
+        HttpParser::from_owned_response(Response::from_parts(parts.clone(), body))
+    }
+}
+impl<T> HttpParser<'_, T> {
+    /// A view of the same message borrowing from `self`, so a variant can tweak options without cloning.
+    fn borrowed(&self) -> HttpParser<'_, T> {
+        let message = match &self.message {
+            Message::Request(request) => Message::Request(Source::Borrowed(&**request)),
+            Message::Response(response) => Message::Response(Source::Borrowed(&**response)),
+        };
+        HttpParser {
+            message,
+            line_ending: self.line_ending,
+            title_case: self.title_case,
+        }
+    }
+    /// The message's version as the typed `http::Version`, for branching without string compares.
+    pub fn version(&self) -> Version {
+        match &self.message {
+            Message::Request(request) => {
                 request.version()
             }
-            HttpParser::Response(response) => {
+            Message::Response(response) => {
                 response.version()
             }
+        }
+    }
+    /// The wrapped request, or `None` for a response.
+    pub fn as_request(&self) -> Option<&Request<T>> {
+        match &self.message {
+            Message::Request(request) => Some(request),
+            Message::Response(_) => None,
+        }
+    }
+    /// The wrapped response, or `None` for a request.
+    pub fn as_response(&self) -> Option<&Response<T>> {
+        match &self.message {
+            Message::Request(_) => None,
+            Message::Response(response) => Some(response),
+        }
+    }
+    /// The method and URI of a request, borrowed for routing without serializing; `None` for responses.
+    pub fn request_target(&self) -> Option<(&Method, &Uri)> {
+        match &self.message {
+            Message::Request(request) => Some((request.method(), request.uri())),
+            Message::Response(_) => None,
+        }
+    }
+    fn parse_version(&self) -> core::result::Result<&'static str, ParseError> {
+        let version = self.version();
+        version_str(version).ok_or(ParseError::UnsupportedVersion(version))
+    }
+    fn headers(&self) -> &HeaderMap {
+        match &self.message {
+            Message::Request(request) => request.headers(),
+            Message::Response(response) => response.headers(),
+        }
+    }
+    /// Uses `HeaderMap::iter()`, which writes each value of a repeated header as its own line but
+    /// groups them under the name's first insertion, so names added `a, b, a` come out `a, a, b`.
+    fn parse_header_bytes_with(&self, headers: &HeaderMap) -> Vec<u8> {
+        let eol = self.line_ending.as_str().as_bytes();
+        let mut bytes = Vec::new();
+        for (key, value) in headers {
+            bytes.extend_from_slice(self.header_name(key).as_bytes());
+            bytes.push(b':');
+            bytes.extend_from_slice(value.as_bytes());
+            bytes.extend_from_slice(eol);
+        }
+        bytes
+    }
+    fn header_name<'n>(&self, name: &'n HeaderName) -> Cow<'n, str> {
+        if self.title_case {
+            Cow::Owned(headers::title_case(name.as_str()))
+        } else {
+            Cow::Borrowed(name.as_str())
+        }
+    }
+    fn parse_start_line(&self) -> core::result::Result<String, ParseError> {
+        let version = self.parse_version()?;
+        match &self.message {
+            Message::Request(request) => {
+                let method = request.method();
+                let target = uri_form::default_target(method, request.uri());
+                Ok(format!("{method} {target} {version}"))
+            }
+            Message::Response(response) => {
+                let status_code = response.status();
+                Ok(format!("{version} {} {}", status_code.as_str(), reason_phrase(status_code)))
+            }
+        }
+    }
+    fn head_bytes(&self) -> core::result::Result<Vec<u8>, ParseError> {
+        self.head_bytes_with(self.headers())
+    }
+    fn head_bytes_with(&self, headers: &HeaderMap) -> core::result::Result<Vec<u8>, ParseError> {
+        if self.version() == Version::HTTP_09 {
+            return Ok(self.simple_head());
+        }
+        let start_line = self.parse_start_line()?;
+        Ok(self.assemble_head(&start_line, headers))
+    }
+    /// The request head `parse()` would write with `method` and `target` in its request line.
+    fn request_head_with(&self, method: &Method, target: &str, headers: &HeaderMap) -> core::result::Result<Vec<u8>, ParseError> {
+        let version = self.parse_version()?;
+        if self.version() == Version::HTTP_09 {
+            return Ok(format!("{method} {target}{}", self.line_ending.as_str()).into_bytes());
+        }
+        Ok(self.assemble_head(&format!("{method} {target} {version}"), headers))
+    }
+    /// HTTP/0.9 has no status line or headers: a request is just `GET /path`, a response just its body.
+    fn simple_head(&self) -> Vec<u8> {
+        let eol = self.line_ending.as_str();
+        self.simple_request_line()
+            .map(|line| format!("{line}{eol}").into_bytes())
+            .unwrap_or_default()
+    }
+    fn simple_request_line(&self) -> Option<String> {
+        let Message::Request(request) = &self.message else {
+            return None;
         };
-        match version {
-            Version::HTTP_09 => Some(String::from("HTTP/0.9")),
-            Version::HTTP_10 => Some(String::from("HTTP/1.0")),
-            Version::HTTP_11 => Some(String::from("HTTP/1.1")),
-            Version::HTTP_2 => Some(String::from("HTTP/2")),
-            Version::HTTP_3 => Some(String::from("HTTP/3")),
-            _ => None,
-        }
-    }
-    fn parse_header(&self) -> String {
-        let headers = match self {
-            HttpParser::Request(request) => {
-                request.headers()
+        let target = uri_form::origin_form(request.uri());
+        Some(format!("{} {target}", request.method()))
+    }
+    /// HTTP/0.9 requests, interim (1xx) responses and `304 Not Modified` end after the head.
+    /// A 304 keeps its `Content-Length`, which describes the full response it stands in for.
+    fn sends_body(&self) -> bool {
+        match &self.message {
+            Message::Request(_) => self.version() != Version::HTTP_09,
+            Message::Response(response) => {
+                let status = response.status();
+                !status.is_informational() && status != StatusCode::NOT_MODIFIED
             }
-            HttpParser::Response(response) => {
-                response.headers()
+        }
+    }
+    fn assemble_head(&self, start_line: &str, headers: &HeaderMap) -> Vec<u8> {
+        let eol = self.line_ending.as_str().as_bytes();
+        let mut bytes = Vec::from(start_line);
+        bytes.extend_from_slice(eol);
+        bytes.extend_from_slice(&self.parse_header_bytes_with(headers));
+        bytes.extend_from_slice(eol);
+        bytes
+    }
+    /// Writes everything `write_into` does except the body. HTTP/0.9 responses have no head.
+    fn write_head_into(&self, headers: &HeaderMap, out: &mut impl WireWrite) -> fmt::Result {
+        let eol = self.line_ending.as_str();
+        let version = self.parse_version().map_err(|_| fmt::Error)?;
+        match &self.message {
+            Message::Request(request) if self.version() == Version::HTTP_09 => {
+                let target = uri_form::origin_form(request.uri());
+                return write!(out, "{} {target}{eol}", request.method());
+            }
+            Message::Response(_) if self.version() == Version::HTTP_09 => return Ok(()),
+            Message::Request(request) => {
+                write!(out, "{} ", request.method())?;
+                uri_form::write_target(out, request.method(), request.uri())?;
+                write!(out, " {version}{eol}")?;
+            }
+            Message::Response(response) => {
+                let status_code = response.status();
+                write!(out, "{version} {} {}{eol}", status_code.as_str(), reason_phrase(status_code))?;
             }
+        }
+        for (name, value) in headers {
+            write!(out, "{}:", self.header_name(name))?;
+            out.write_value(value.as_bytes())?;
+            out.write_str(eol)?;
+        }
+        out.write_str(eol)
+    }
+    /// The message body, untouched; pairs with `parse_head()` or `response_head()` for streaming.
+    pub fn body(&self) -> &T {
+        match &self.message {
+            Message::Request(request) => request.body(),
+            Message::Response(response) => response.body(),
+        }
+    }
+}
+impl<T> HttpParser<'_, T> {
+    /// The raw header lines, with values copied byte-for-byte. This is what the byte-oriented
+    /// serializers write; only the `String`-returning APIs convert values with `from_utf8_lossy`.
+    pub fn parse_header_bytes(&self) -> Vec<u8> {
+        self.parse_header_bytes_with(self.headers())
+    }
+    /// Just the request line (`GET / HTTP/1.1`) or status line (`HTTP/1.1 200 OK`).
+    pub fn start_line(&self) -> core::result::Result<String, ParseError> {
+        self.parse_start_line()
+    }
+    /// The start line and headers up to and including the blank separator line, without the body.
+    pub fn parse_head(&self) -> core::result::Result<String, ParseError> {
+        let head = self.head_bytes()?;
+        Ok(String::from_utf8_lossy(&head).into_owned())
+    }
+    /// Writes the head, then copies `body` into `w`, ignoring the message's own body.
+    /// Returns the total number of bytes written.
+    #[cfg(feature = "std")]
+    pub fn write_with_body<W: Write, R: io::Read>(&self, w: &mut W, body: &mut R) -> io::Result<u64> {
+        let head = self.head_bytes()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        w.write_all(&head)?;
+        Ok(head.len() as u64 + io::copy(body, w)?)
+    }
+    /// The status line and headers of a response, so they can be sent before streaming `body()`.
+    pub fn response_head(&self) -> core::result::Result<String, ParseError> {
+        let Message::Response(_) = &self.message else {
+            return Err(ParseError::WrongVariant);
         };
-        headers.iter()
-            .map(|(key, value)| {
-                let value = String::from_utf8_lossy(value.as_bytes());
-                format!("{key}:{value}")
-            })
-            .collect::<Vec<_>>()
-            .join("\r\n")
+        self.parse_head()
     }
-    fn parse_request(&self) -> Option<String> {
-        if let HttpParser::Request(request) = self {
-            let method = request.method();
-            let uri = request.uri();
-            let version = self.parse_version()?;
-            let header = self.parse_header();
-            let body = request.body();
-
-            return Some(format!("{method} {uri} {version}\r\n{header}\r\n{body}"));
+    /// Serializes a response to `HEAD`: the headers, `Content-Length` included, as a GET would
+    /// get them, but no body.
+    pub fn parse_head_response(&self) -> core::result::Result<String, ParseError> {
+        self.response_head()
+    }
+    /// The serialized head plus a borrow of the untouched body, for streaming the body separately.
+    pub fn split_head(&self) -> core::result::Result<(String, &T), ParseError> {
+        Ok((self.parse_head()?, self.body()))
+    }
+}
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    fn body_bytes(&self) -> &[u8] {
+        if self.sends_body() {
+            self.body().as_ref()
+        } else {
+            &[]
         }
-        None
     }
-    fn parse_response(&self) -> Option<String> {
-        if let HttpParser::Response(response) = self {
-            let version = self.parse_version()?;
-            let status_code = response.status();
-            let header = self.parse_header();
-            let body = response.body();
-
-            return Some(format!("{version} {status_code}\r\n{header}\r\n{body}"));
+    /// The body's length in bytes, without serializing anything.
+    pub fn body_len(&self) -> usize {
+        self.body().as_ref().len()
+    }
+    pub fn parse_bytes(&self) -> core::result::Result<Vec<u8>, ParseError> {
+        let mut bytes = self.head_bytes()?;
+        bytes.extend_from_slice(self.body_bytes());
+        Ok(bytes)
+    }
+    /// The `parse_bytes()` output and the offset where the body starts, right after the head.
+    pub fn serialize_with_offsets(&self) -> core::result::Result<(Vec<u8>, usize), ParseError> {
+        let mut bytes = self.head_bytes()?;
+        let offset = bytes.len();
+        bytes.extend_from_slice(self.body_bytes());
+        Ok((bytes, offset))
+    }
+    /// The exact number of bytes `parse_bytes()` would produce, computed without building the message.
+    pub fn serialized_len(&self) -> core::result::Result<usize, ParseError> {
+        if self.version() == Version::HTTP_09 {
+            return Ok(self.simple_head().len() + self.body_bytes().len());
         }
-        None
+        let start_line = self.parse_start_line()?;
+        let eol = self.line_ending.as_str().len();
+        let header = self.headers()
+            .iter()
+            .map(|(key, value)| key.as_str().len() + 1 + value.len() + eol)
+            .sum::<usize>();
+        Ok(start_line.len() + eol + header + eol + self.body_bytes().len())
+    }
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let head = self.head_bytes()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        w.write_all(&head)?;
+        w.write_all(self.body_bytes())
     }
 }
 impl<T: Display> HttpParser<'_, T> {
-    pub fn parse(&self) -> Option<String> {
-        match self {
-            HttpParser::Request(_) => self.parse_request(),
-            HttpParser::Response(_) => self.parse_response(),
+    /// Header values that are not valid UTF-8 are converted lossily; use `parse_bytes()` to keep them intact.
+    /// Headers cannot inject lines: `HeaderName` and `HeaderValue` refuse CR, LF and NUL when built.
+    pub fn parse(&self) -> core::result::Result<String, ParseError> {
+        self.parse_with_headers(self.headers())
+    }
+    /// Like `parse()`, but clears and reuses `buf` so a hot loop can keep one allocation.
+    pub fn parse_into(&self, buf: &mut String) -> core::result::Result<(), ParseError> {
+        buf.clear();
+        self.write_message(self.headers(), buf)
+    }
+    /// Like `parse()`, but fails with `BodyTooLarge` if the formatted body exceeds `max` bytes.
+    /// The body is measured without being buffered, so nothing large is allocated first.
+    pub fn parse_max_body(&self, max: usize) -> core::result::Result<String, ParseError> {
+        let mut size = ByteCount(0);
+        if self.sends_body() {
+            fmt::Write::write_fmt(&mut size, format_args!("{}", self.body())).map_err(|_| ParseError::BodyFormat)?;
         }
+        if size.0 > max {
+            return Err(ParseError::BodyTooLarge { size: size.0, max });
+        }
+        self.parse()
+    }
+    /// Like `parse()`, with `Content-Length` set to the body's byte length. Interim (1xx) and
+    /// `304` responses are left as `parse()` writes them, since they send no body.
+    pub fn parse_with_content_length(&self) -> core::result::Result<String, ParseError> {
+        if !self.sends_body() {
+            return self.parse();
+        }
+        let body = self.body_string();
+        let mut headers = self.headers().clone();
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        let head = self.head_bytes_with(&headers)?;
+        Ok(Self::join_head(head, &body))
+    }
+    /// Like `parse()`, but a bodyless POST, PUT or PATCH request without framing headers gets an
+    /// explicit `Content-Length: 0`, as some strict servers require. Other messages are unchanged.
+    pub fn parse_explicit_zero_length(&self) -> core::result::Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return self.parse();
+        };
+        let carries_body = matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH);
+        let framed = request.headers().contains_key(header::CONTENT_LENGTH)
+            || request.headers().contains_key(header::TRANSFER_ENCODING);
+        if !carries_body || framed || !self.body_string().is_empty() {
+            return self.parse();
+        }
+        let mut headers = request.headers().clone();
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(0));
+        self.parse_with_headers(&headers)
+    }
+    pub fn parse_response_with_reason(&self, reason: &str) -> core::result::Result<String, ParseError> {
+        let Message::Response(response) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let version = self.parse_version()?;
+        let start_line = format!("{version} {} {reason}", response.status().as_str());
+        let head = self.assemble_head(&start_line, response.headers());
+        Ok(Self::join_head(head, &self.body_string()))
+    }
+    /// Yields the start line, each header line, an empty separator line and the body, so that
+    /// joining the items with the line ending reproduces `parse()`.
+    pub fn lines(&self) -> core::result::Result<impl Iterator<Item = String> + '_, ParseError> {
+        let head = if self.version() == Version::HTTP_09 {
+            self.simple_request_line().into_iter().collect::<Vec<_>>()
+        } else {
+            let mut head = vec![self.parse_start_line()?];
+            head.extend(self.headers().iter().map(|(key, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes());
+                format!("{}:{value}", self.header_name(key))
+            }));
+            head.push(String::new());
+            head
+        };
+        let body = core::iter::once_with(|| self.body_string());
+        Ok(head.into_iter().chain(body))
+    }
+    fn parse_with_headers(&self, headers: &HeaderMap) -> core::result::Result<String, ParseError> {
+        self.parse_version()?;
+        // The body's length is unknown until it is formatted, so leave some headroom for small ones.
+        let head_len = headers.iter()
+            .map(|(name, value)| name.as_str().len() + value.len() + 3)
+            .sum::<usize>();
+        let mut message = String::with_capacity(head_len + 128);
+        self.write_message(headers, &mut message)?;
+        Ok(message)
+    }
+    fn write_message(&self, headers: &HeaderMap, buf: &mut String) -> core::result::Result<(), ParseError> {
+        self.parse_version()?;
+        // Writing to a `String` cannot fail and the version is checked, so only the body's `Display` can.
+        self.write_into(headers, buf).map_err(|_| ParseError::BodyFormat)
+    }
+    /// Writes the start line, headers and body in a single pass without intermediate strings.
+    /// Header values that are not valid UTF-8 are converted lossily, as in `parse()`.
+    fn write_into(&self, headers: &HeaderMap, out: &mut impl WireWrite) -> fmt::Result {
+        self.write_head_into(headers, out)?;
+        if self.sends_body() {
+            write!(out, "{}", self.body())?;
+        }
+        Ok(())
+    }
+    fn body_string(&self) -> String {
+        if self.sends_body() {
+            self.body().to_string()
+        } else {
+            String::new()
+        }
+    }
+    fn join_head(head: Vec<u8>, body: &str) -> String {
+        let mut message = String::from_utf8_lossy(&head).into_owned();
+        message.push_str(body);
+        message
+    }
+}
+/// A `fmt::Write` sink for serialized messages that decides how raw header values are written.
+trait WireWrite: fmt::Write {
+    fn write_value(&mut self, value: &[u8]) -> fmt::Result {
+        self.write_str(&String::from_utf8_lossy(value))
+    }
+}
+impl WireWrite for String {}
+/// A `fmt::Write` sink that only counts the bytes written to it.
+struct ByteCount(usize);
+impl fmt::Write for ByteCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+impl<T: Display> TryFrom<HttpParser<'_, T>> for String {
+    type Error = ParseError;
+
+    fn try_from(parser: HttpParser<'_, T>) -> core::result::Result<Self, Self::Error> {
+        parser.parse()
     }
 }
 #[cfg(test)]
 mod tests {
-    use crate::HttpParser;
-    use http::{Method, Request, Response, StatusCode, Version};
+    use crate::{version_from_str, version_str, HttpParser, LineEnding, ParseError};
+    use http::{HeaderValue, Method, Request, Response, StatusCode, Version};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// Counts allocations per thread so tests running in parallel don't disturb each other.
+    struct CountingAllocator;
+    std::thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
 
     #[test]
     fn it_works() {
@@ -105,4 +654,605 @@ mod tests {
         assert_eq!(request, "GET http://localhost/ HTTP/1.1\r\n\r\n");
         assert_eq!(response, "HTTP/1.1 200 OK\r\n\r\n<h1>hello</h1>");
     }
-}
\ No newline at end of file
+    #[test]
+    fn parse_keeps_duplicate_headers_in_order() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("set-cookie", "a=1")
+            .header("content-type", "text/html")
+            .header("set-cookie", "b=2")
+            .header("set-cookie", "c=3")
+            .body("")
+            .unwrap();
+        let response = HttpParser::from_response(&response).parse().unwrap();
+        assert_eq!(
+            response,
+            "HTTP/1.1 200 OK\r\nset-cookie:a=1\r\nset-cookie:b=2\r\nset-cookie:c=3\r\ncontent-type:text/html\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_bytes_keeps_binary_body() {
+        let png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00];
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "image/png")
+            .body(png.clone())
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_bytes().unwrap();
+        let head = b"HTTP/1.1 200 OK\r\ncontent-type:image/png\r\n\r\n";
+        assert_eq!(&bytes[..head.len()], head);
+        assert_eq!(&bytes[head.len()..], png.as_slice());
+    }
+    #[test]
+    fn parse_bytes_matches_parse() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/html")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse_bytes().unwrap(), parser.parse().unwrap().into_bytes());
+    }
+    #[test]
+    fn parse_response_reason_phrase() {
+        let response = Response::builder()
+            .status(StatusCode::from_u16(599).unwrap())
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse().unwrap(), "HTTP/1.1 599 \r\n\r\n");
+        assert_eq!(
+            parser.parse_response_with_reason("Custom Error").unwrap(),
+            "HTTP/1.1 599 Custom Error\r\n\r\n"
+        );
+
+        let response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap();
+        assert_eq!(HttpParser::from_response(&response).parse().unwrap(), "HTTP/1.1 404 Not Found\r\n\r\n");
+    }
+    #[test]
+    fn parse_response_with_reason_rejects_requests() {
+        let request = Request::builder().body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_response_with_reason("OK").unwrap_err(),
+            ParseError::WrongVariant
+        );
+    }
+    #[test]
+    fn serialized_len_matches_parse_bytes() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/html; charset=utf-8")
+            .header("set-cookie", "a=1")
+            .header("set-cookie", "b=2")
+            .body("<h1>héllo</h1>")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.serialized_len().unwrap(), parser.parse_bytes().unwrap().len());
+
+        let request = Request::builder().uri("http://localhost/").body(Vec::new()).unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.serialized_len().unwrap(), parser.parse_bytes().unwrap().len());
+    }
+    #[test]
+    fn line_ending_lf() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header("host", "localhost")
+            .header("accept", "*/*")
+            .body("")
+            .unwrap();
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/html")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let request = HttpParser::from_request(&request).with_line_ending(LineEnding::Lf);
+        let response = HttpParser::from_response(&response).with_line_ending(LineEnding::Lf);
+        assert_eq!(request.parse().unwrap(), "GET / HTTP/1.1\nhost:localhost\naccept:*/*\n\n");
+        assert_eq!(response.parse().unwrap(), "HTTP/1.1 200 OK\ncontent-type:text/html\n\n<h1>hello</h1>");
+        assert_eq!(response.parse_bytes().unwrap().len(), response.serialized_len().unwrap());
+        #[cfg(feature = "std")]
+        {
+            let mut sink = Vec::new();
+            response.write_to(&mut sink).unwrap();
+            assert_eq!(sink, response.parse_bytes().unwrap());
+        }
+    }
+    #[test]
+    fn version_accessor_matches_start_line() {
+        for version in [Version::HTTP_09, Version::HTTP_10, Version::HTTP_11, Version::HTTP_2, Version::HTTP_3] {
+            let response = Response::builder().status(StatusCode::OK).version(version).body("").unwrap();
+            let parser = HttpParser::from_response(&response);
+            assert_eq!(parser.version(), version);
+            assert_eq!(parser.parse_version().unwrap(), version_str(version).unwrap());
+            assert!(parser.start_line().unwrap().starts_with(version_str(version).unwrap()));
+        }
+    }
+    #[test]
+    fn version_str_matches_tokens() {
+        let tokens = [
+            (Version::HTTP_09, "HTTP/0.9"),
+            (Version::HTTP_10, "HTTP/1.0"),
+            (Version::HTTP_11, "HTTP/1.1"),
+            (Version::HTTP_2, "HTTP/2"),
+            (Version::HTTP_3, "HTTP/3"),
+        ];
+        for (version, token) in tokens {
+            assert_eq!(version_str(version), Some(token));
+            assert_eq!(version_from_str(token), Some(version));
+        }
+        assert_eq!(version_from_str("HTTP/9.9"), None);
+        assert_eq!(version_from_str("http/1.1"), None);
+    }
+    #[test]
+    fn parse_allocates_once() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/submit?a=1")
+            .version(Version::HTTP_11)
+            .header("host", "localhost")
+            .header("content-type", "text/plain")
+            .body("hello")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let before = ALLOCATIONS.with(Cell::get);
+        let message = parser.parse().unwrap();
+        assert_eq!(ALLOCATIONS.with(Cell::get) - before, 1);
+        assert_eq!(
+            message,
+            "POST http://localhost/submit?a=1 HTTP/1.1\r\nhost:localhost\r\ncontent-type:text/plain\r\n\r\nhello"
+        );
+    }
+    #[test]
+    fn parse_into_reuses_buffer() {
+        let first = Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "text/html")
+            .body("<p>one</p>")
+            .unwrap();
+        let second = Response::builder().status(StatusCode::NOT_FOUND).body("missing").unwrap();
+        let mut buf = String::new();
+        HttpParser::from_response(&first).parse_into(&mut buf).unwrap();
+        assert_eq!(buf, HttpParser::from_response(&first).parse().unwrap());
+        let capacity = buf.capacity();
+        HttpParser::from_response(&second).parse_into(&mut buf).unwrap();
+        assert_eq!(buf, HttpParser::from_response(&second).parse().unwrap());
+        assert_eq!(buf.capacity(), capacity);
+    }
+    #[test]
+    fn informational_responses_omit_body() {
+        for status in [StatusCode::CONTINUE, StatusCode::from_u16(103).unwrap()] {
+            let response = Response::builder()
+                .status(status)
+                .version(Version::HTTP_11)
+                .header("link", "</style.css>; rel=preload")
+                .body("accidental")
+                .unwrap();
+            let parser = HttpParser::from_response(&response);
+            let head = parser.parse_head().unwrap();
+            assert!(head.ends_with("\r\n\r\n"));
+            assert_eq!(parser.parse().unwrap(), head);
+            assert_eq!(parser.parse_bytes().unwrap(), head.as_bytes());
+            assert_eq!(parser.serialized_len().unwrap(), head.len());
+        }
+        let response = Response::builder().status(StatusCode::CONTINUE).body("accidental").unwrap();
+        assert_eq!(HttpParser::from_response(&response).parse().unwrap(), "HTTP/1.1 100 Continue\r\n\r\n");
+    }
+    #[test]
+    fn try_from_parser_for_string() {
+        let response = Response::builder().status(StatusCode::OK).body("ok").unwrap();
+        let message = String::try_from(HttpParser::from_response(&response)).unwrap();
+        assert_eq!(message, "HTTP/1.1 200 OK\r\n\r\nok");
+
+        let convert = |version| -> Result<String, ParseError> {
+            let request = Request::builder().uri("/").version(version).body("").unwrap();
+            let converted: String = HttpParser::from_owned_request(request).try_into()?;
+            Ok(converted)
+        };
+        assert_eq!(convert(Version::HTTP_3).unwrap(), "GET / HTTP/3\r\n\r\n");
+        assert_eq!(convert(Version::HTTP_09).unwrap(), "GET /\r\n");
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_with_body_streams_reader() {
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/upload")
+            .header("content-length", "11")
+            .body(())
+            .unwrap();
+        let mut sink = Vec::new();
+        let mut body = std::io::Cursor::new(b"hello world");
+        let written = HttpParser::from_request(&request).write_with_body(&mut sink, &mut body).unwrap();
+        assert_eq!(sink, b"PUT /upload HTTP/1.1\r\ncontent-length:11\r\n\r\nhello world");
+        assert_eq!(written, sink.len() as u64);
+    }
+    #[test]
+    fn from_parts_matches_intact_message() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/submit")
+            .header("content-type", "text/plain")
+            .body(String::from("name=value"))
+            .unwrap();
+        let expected = HttpParser::from_request(&request).parse().unwrap();
+        let (parts, body) = request.into_parts();
+        assert_eq!(HttpParser::from_request_parts(&parts, &body).parse().unwrap(), expected);
+
+        let response = Response::builder()
+            .status(StatusCode::CREATED)
+            .header("location", "/items/1")
+            .body(b"created".to_vec())
+            .unwrap();
+        let expected = HttpParser::from_response(&response).parse_bytes().unwrap();
+        let (parts, body) = response.into_parts();
+        assert_eq!(HttpParser::from_response_parts(&parts, &body).parse_bytes().unwrap(), expected);
+    }
+    #[test]
+    fn body_len_counts_bytes() {
+        let response = Response::builder().status(StatusCode::OK).body("héllo wörld").unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.body_len(), 13);
+        assert_ne!(parser.body_len(), response.body().chars().count());
+        assert_eq!(parser.body_len(), parser.parse_bytes().unwrap().len() - parser.parse_head().unwrap().len());
+    }
+    #[test]
+    fn split_head_borrows_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/plain")
+            .body(String::from("hello"))
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let (head, body) = parser.split_head().unwrap();
+        assert!(core::ptr::eq(body, response.body()));
+        assert_eq!(head + body, parser.parse().unwrap());
+    }
+    #[test]
+    fn request_target_borrows_method_and_uri() {
+        let request = Request::builder().method(Method::DELETE).uri("/items/7?force=1").body("").unwrap();
+        let parser = HttpParser::from_request(&request);
+        let (method, uri) = parser.request_target().unwrap();
+        assert_eq!(method, Method::DELETE);
+        assert_eq!(uri.path(), "/items/7");
+        assert!(core::ptr::eq(uri, request.uri()));
+
+        let response = Response::builder().status(StatusCode::OK).body("").unwrap();
+        assert_eq!(HttpParser::from_response(&response).request_target(), None);
+    }
+    #[test]
+    fn as_request_and_as_response_replace_variants() {
+        let request = Request::builder().uri("/a").body("").unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert!(core::ptr::eq(parser.as_request().unwrap(), &request));
+        assert!(parser.as_response().is_none());
+
+        let parser = HttpParser::from_owned_response(Response::builder().status(StatusCode::CREATED).body("").unwrap());
+        assert_eq!(parser.as_response().unwrap().status(), StatusCode::CREATED);
+        assert!(parser.as_request().is_none());
+    }
+    #[test]
+    fn not_modified_omits_body() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .version(Version::HTTP_11)
+            .header("content-length", "1024")
+            .body("accidental body")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let expected = "HTTP/1.1 304 Not Modified\r\ncontent-length:1024\r\n\r\n";
+        assert_eq!(parser.parse().unwrap(), expected);
+        assert_eq!(parser.parse_bytes().unwrap(), expected.as_bytes());
+        assert_eq!(parser.serialized_len().unwrap(), expected.len());
+    }
+    #[test]
+    fn response_head_pairs_with_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-length", "5")
+            .body(String::from("hello"))
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let head = parser.response_head().unwrap();
+        assert_eq!(head, "HTTP/1.1 200 OK\r\ncontent-length:5\r\n\r\n");
+        assert_eq!(head + parser.body(), parser.parse().unwrap());
+
+        let request = Request::builder().uri("/").body(String::new()).unwrap();
+        assert_eq!(HttpParser::from_request(&request).response_head().unwrap_err(), ParseError::WrongVariant);
+    }
+    #[test]
+    fn parse_max_body_enforces_limit() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("0123456789")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse_max_body(10).unwrap(), parser.parse().unwrap());
+        assert_eq!(parser.parse_max_body(9).unwrap_err(), ParseError::BodyTooLarge { size: 10, max: 9 });
+
+        let interim = Response::builder().status(StatusCode::CONTINUE).body("ignored").unwrap();
+        assert!(HttpParser::from_response(&interim).parse_max_body(0).is_ok());
+    }
+    #[test]
+    fn parse_head_response_keeps_content_length() {
+        let body = "x".repeat(100);
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-length", "100")
+            .body(body)
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_head_response().unwrap(),
+            "HTTP/1.1 200 OK\r\ncontent-length:100\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_head_stops_before_body() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/html")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let head = parser.parse_head().unwrap();
+        let message = parser.parse().unwrap();
+        assert_eq!(head, "HTTP/1.1 200 OK\r\ncontent-type:text/html\r\n\r\n");
+        assert_eq!(head, message[..message.len() - response.body().len()]);
+    }
+    #[test]
+    fn parse_header_bytes_keeps_opaque_values() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("x-opaque", HeaderValue::from_bytes(b"caf\xe9").unwrap())
+            .body("")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        assert_eq!(parser.parse_header_bytes(), b"x-opaque:caf\xe9\r\n");
+        assert!(parser.parse_bytes().unwrap().ends_with(b"x-opaque:caf\xe9\r\n\r\n"));
+        assert!(parser.parse().unwrap().ends_with("x-opaque:caf\u{fffd}\r\n\r\n"));
+    }
+    #[test]
+    fn http_09_simple_messages() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost/index.html")
+            .version(Version::HTTP_09)
+            .header("host", "localhost")
+            .body("ignored")
+            .unwrap();
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_09)
+            .header("content-type", "text/html")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let request = HttpParser::from_request(&request);
+        let response = HttpParser::from_response(&response);
+        assert_eq!(request.parse().unwrap(), "GET /index.html\r\n");
+        assert_eq!(request.parse_bytes().unwrap(), b"GET /index.html\r\n");
+        assert_eq!(request.serialized_len().unwrap(), 17);
+        assert_eq!(response.parse().unwrap(), "<h1>hello</h1>");
+        assert_eq!(response.parse_bytes().unwrap(), b"<h1>hello</h1>");
+        assert_eq!(response.serialized_len().unwrap(), 14);
+    }
+    #[test]
+    fn owned_parser_matches_borrowed() {
+        fn build() -> Response<&'static str> {
+            Response::builder()
+                .status(StatusCode::OK)
+                .version(Version::HTTP_11)
+                .header("content-type", "text/html")
+                .body("<h1>hello</h1>")
+                .unwrap()
+        }
+        let response = build();
+        assert_eq!(
+            HttpParser::from_owned_response(build()).parse().unwrap(),
+            HttpParser::from_response(&response).parse().unwrap()
+        );
+        let request = HttpParser::from_owned_request(Request::builder().uri("/").body(Vec::new()).unwrap())
+            .parse_bytes()
+            .unwrap();
+        assert_eq!(request, b"GET / HTTP/1.1\r\n\r\n");
+    }
+    #[test]
+    fn lines_join_to_parse() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/html")
+            .header("set-cookie", "a=1")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let lines = parser.lines().unwrap().collect::<Vec<_>>();
+        assert_eq!(lines, ["HTTP/1.1 200 OK", "content-type:text/html", "set-cookie:a=1", "", "<h1>hello</h1>"]);
+        assert_eq!(lines.join("\r\n"), parser.parse().unwrap());
+
+        let request = Request::builder().uri("/").body("").unwrap();
+        let parser = HttpParser::from_request(&request).with_line_ending(LineEnding::Lf);
+        assert_eq!(parser.lines().unwrap().collect::<Vec<_>>().join("\n"), parser.parse().unwrap());
+
+        let request = Request::builder().uri("/").version(Version::HTTP_09).body("").unwrap();
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(parser.lines().unwrap().collect::<Vec<_>>().join("\r\n"), parser.parse().unwrap());
+    }
+    #[test]
+    fn start_line_is_first_line_of_parse() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header("host", "localhost")
+            .body("")
+            .unwrap();
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let request = HttpParser::from_request(&request);
+        let response = HttpParser::from_response(&response);
+        assert_eq!(request.start_line().unwrap(), "GET / HTTP/1.1");
+        assert_eq!(response.start_line().unwrap(), "HTTP/1.1 200 OK");
+        assert_eq!(request.parse().unwrap().split("\r\n").next().unwrap(), request.start_line().unwrap());
+        assert_eq!(response.parse().unwrap().split("\r\n").next().unwrap(), response.start_line().unwrap());
+    }
+    #[test]
+    fn parse_connect_uses_authority_form() {
+        let request = Request::builder()
+            .method(Method::CONNECT)
+            .uri("example.com:443")
+            .version(Version::HTTP_11)
+            .header("host", "example.com:443")
+            .body("")
+            .unwrap();
+        let request = HttpParser::from_request(&request).parse().unwrap();
+        assert_eq!(request, "CONNECT example.com:443 HTTP/1.1\r\nhost:example.com:443\r\n\r\n");
+
+        let request = Request::builder()
+            .method(Method::CONNECT)
+            .uri("https://example.com:443/ignored")
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap();
+        let request = HttpParser::from_request(&request).parse().unwrap();
+        assert_eq!(request, "CONNECT example.com:443 HTTP/1.1\r\n\r\n");
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_to_matches_parse() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/upload")
+            .version(Version::HTTP_11)
+            .header("host", "localhost")
+            .header("content-type", "text/plain")
+            .body("payload")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let mut sink = Vec::new();
+        parser.write_to(&mut sink).unwrap();
+        assert_eq!(sink, parser.parse().unwrap().into_bytes());
+    }
+    #[test]
+    fn parse_with_content_length_uses_byte_length() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .body("héllo")
+            .unwrap();
+        let response = HttpParser::from_response(&response).parse_with_content_length().unwrap();
+        assert_eq!(response, "HTTP/1.1 200 OK\r\ncontent-length:6\r\n\r\nhéllo");
+    }
+    #[test]
+    fn parse_with_content_length_overrides_existing() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-length", "1")
+            .header("content-length", "2")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        let response = HttpParser::from_response(&response).parse_with_content_length().unwrap();
+        assert_eq!(response, "HTTP/1.1 200 OK\r\ncontent-length:14\r\n\r\n<h1>hello</h1>");
+
+        let not_modified = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .version(Version::HTTP_11)
+            .header("content-length", "1024")
+            .body("<h1>hello</h1>")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&not_modified).parse_with_content_length().unwrap(),
+            "HTTP/1.1 304 Not Modified\r\ncontent-length:1024\r\n\r\n"
+        );
+        let interim = Response::builder()
+            .status(StatusCode::CONTINUE)
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_response(&interim).parse_with_content_length().unwrap(),
+            "HTTP/1.1 100 Continue\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_explicit_zero_length_only_for_body_methods() {
+        let request = |method: Method| {
+            Request::builder()
+                .method(method)
+                .uri("/items")
+                .version(Version::HTTP_11)
+                .header("host", "example.com")
+                .body("")
+                .unwrap()
+        };
+        let post = request(Method::POST);
+        assert_eq!(
+            HttpParser::from_request(&post).parse_explicit_zero_length().unwrap(),
+            "POST /items HTTP/1.1\r\nhost:example.com\r\ncontent-length:0\r\n\r\n"
+        );
+        let get = request(Method::GET);
+        assert_eq!(
+            HttpParser::from_request(&get).parse_explicit_zero_length().unwrap(),
+            "GET /items HTTP/1.1\r\nhost:example.com\r\n\r\n"
+        );
+        let mut chunked = request(Method::PUT);
+        chunked.headers_mut().insert("transfer-encoding", HeaderValue::from_static("chunked"));
+        let parser = HttpParser::from_request(&chunked);
+        assert_eq!(parser.parse_explicit_zero_length(), parser.parse());
+    }
+    #[test]
+    fn serialize_with_offsets_marks_body_start() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header("content-type", "text/plain")
+            .body("body bytes")
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let (bytes, offset) = parser.serialize_with_offsets().unwrap();
+        assert_eq!(&bytes[..offset], b"HTTP/1.1 200 OK\r\ncontent-type:text/plain\r\n\r\n");
+        assert_eq!(&bytes[offset..], b"body bytes");
+        assert_eq!(bytes, parser.parse_bytes().unwrap());
+    }
+    struct FailingBody;
+    impl core::fmt::Display for FailingBody {
+        fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Err(core::fmt::Error)
+        }
+    }
+    #[test]
+    fn parse_reports_body_format_errors() {
+        let response = Response::builder().status(StatusCode::OK).body(FailingBody).unwrap();
+        assert_eq!(HttpParser::from_response(&response).parse().unwrap_err(), ParseError::BodyFormat);
+        assert_eq!(HttpParser::from_response(&response).parse_max_body(10).unwrap_err(), ParseError::BodyFormat);
+    }
+    #[test]
+    fn early_hints_reason_is_shared() {
+        let hints = Response::builder().status(103).version(Version::HTTP_11).body("").unwrap();
+        let parser = HttpParser::from_response(&hints);
+        assert_eq!(parser.parse().unwrap(), "HTTP/1.1 103 Early Hints\r\n\r\n");
+        assert_eq!(parser.parse_bytes().unwrap(), b"HTTP/1.1 103 Early Hints\r\n\r\n");
+        assert_eq!(parser.start_line().unwrap(), "HTTP/1.1 103 Early Hints");
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{chunked, decode, framing, HttpParser, ParseError};
+use http::{HeaderMap, Request, Response};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[derive(Clone, Debug)]
+pub enum HttpMessage {
+    Request(Request<Vec<u8>>),
+    Response(Response<Vec<u8>>),
+}
+impl HttpMessage {
+    pub fn parser(&self) -> HttpParser<'_, Vec<u8>> {
+        match self {
+            HttpMessage::Request(request) => HttpParser::from_request(request),
+            HttpMessage::Response(response) => HttpParser::from_response(response),
+        }
+    }
+    /// Equality that ignores header order; repeated headers must still match in count and value.
+    pub fn eq_ignore_header_order(&self, other: &HttpMessage) -> bool {
+        let same_start = match (self, other) {
+            (HttpMessage::Request(a), HttpMessage::Request(b)) => {
+                a.method() == b.method() && a.uri() == b.uri() && a.version() == b.version()
+            }
+            (HttpMessage::Response(a), HttpMessage::Response(b)) => {
+                a.status() == b.status() && a.version() == b.version()
+            }
+            _ => false,
+        };
+        same_start && sorted_headers(self.headers()) == sorted_headers(other.headers()) && self.body() == other.body()
+    }
+    fn headers(&self) -> &HeaderMap {
+        match self {
+            HttpMessage::Request(request) => request.headers(),
+            HttpMessage::Response(response) => response.headers(),
+        }
+    }
+    pub(crate) fn headers_mut(&mut self) -> &mut HeaderMap {
+        match self {
+            HttpMessage::Request(request) => request.headers_mut(),
+            HttpMessage::Response(response) => response.headers_mut(),
+        }
+    }
+    fn body(&self) -> &[u8] {
+        match self {
+            HttpMessage::Request(request) => request.body(),
+            HttpMessage::Response(response) => response.body(),
+        }
+    }
+}
+fn sorted_headers(headers: &HeaderMap) -> Vec<(&str, &[u8])> {
+    let mut pairs = headers.iter()
+        .map(|(name, value)| (name.as_str(), value.as_bytes()))
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    pairs
+}
+impl From<Request<Vec<u8>>> for HttpMessage {
+    fn from(request: Request<Vec<u8>>) -> Self {
+        HttpMessage::Request(request)
+    }
+}
+impl From<Response<Vec<u8>>> for HttpMessage {
+    fn from(response: Response<Vec<u8>>) -> Self {
+        HttpMessage::Response(response)
+    }
+}
+impl Display for HttpMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let bytes = self.parser().parse_bytes().map_err(|_| core::fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&bytes))
+    }
+}
+impl FromStr for HttpMessage {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("HTTP/") {
+            let response = HttpParser::parse_response_str(s)?;
+            Ok(HttpMessage::Response(response.map(String::into_bytes)))
+        } else {
+            let request = HttpParser::parse_request_str(s)?;
+            Ok(HttpMessage::Request(request.map(String::into_bytes)))
+        }
+    }
+}
+impl TryFrom<&[u8]> for HttpMessage {
+    type Error = ParseError;
+
+    /// Parses a raw message off the wire, keeping the body as bytes. A `Content-Length`
+    /// that disagrees with the bytes after the head (e.g. a truncated buffer) is an error,
+    /// except on 1xx, 204 and 304 responses, whose framing headers describe no sent body.
+    /// A chunked body is decoded, and `chunked` is replaced by a `Content-Length`. With the
+    /// `compression` feature, `gzip`, `deflate` and `br` content codings are decoded as well,
+    /// failing with `DecodedBodyTooLarge` past 64 MiB.
+    fn try_from(raw: &[u8]) -> Result<Self, Self::Error> {
+        let (head, body) = decode::split_head(raw)?;
+        let mut message = if head.starts_with("HTTP/") {
+            let response = decode::parse_response_head(head)?;
+            HttpMessage::Response(response.map(|_| body.to_vec()))
+        } else {
+            let request = decode::parse_request_head(head)?;
+            HttpMessage::Request(request.map(|_| body.to_vec()))
+        };
+        if let HttpMessage::Response(response) = &message && framing::is_bodiless(response.status()) {
+            return Ok(message);
+        }
+        if chunked::is_chunked(message.headers()) {
+            let payload = chunked::decode_chunked(body)?;
+            chunked::unchunk_headers(message.headers_mut(), payload.len());
+            match &mut message {
+                HttpMessage::Request(request) => *request.body_mut() = payload,
+                HttpMessage::Response(response) => *response.body_mut() = payload,
+            }
+        } else {
+            decode::check_declared_length(message.headers(), body.len())?;
+        }
+        #[cfg(feature = "compression")]
+        match &mut message {
+            HttpMessage::Request(request) => {
+                let mut payload = core::mem::take(request.body_mut());
+                crate::compression::decode_content(request.headers_mut(), &mut payload, crate::compression::MAX_DECODED_LEN)?;
+                *request.body_mut() = payload;
+            }
+            HttpMessage::Response(response) => {
+                let mut payload = core::mem::take(response.body_mut());
+                crate::compression::decode_content(response.headers_mut(), &mut payload, crate::compression::MAX_DECODED_LEN)?;
+                *response.body_mut() = payload;
+            }
+        }
+        Ok(message)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpMessage, HttpParser, ParseError};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn display_matches_parse() {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/index.html")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .body(b"hello".to_vec())
+            .unwrap();
+        let expected = HttpParser::from_request(&request).parse_bytes().unwrap();
+        let message = HttpMessage::from(request);
+        assert_eq!(message.to_string().into_bytes(), expected);
+    }
+    #[test]
+    fn from_str_round_trip() {
+        let raw = "HTTP/1.1 200 OK\r\ncontent-type:text/html\r\n\r\n<h1>hello</h1>";
+        let message: HttpMessage = raw.parse().unwrap();
+        let HttpMessage::Response(response) = &message else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), b"<h1>hello</h1>");
+        assert_eq!(message.to_string(), raw);
+
+        let raw = "DELETE /item/1 HTTP/1.0\r\nhost:localhost\r\n\r\n";
+        let message: HttpMessage = raw.parse().unwrap();
+        assert!(matches!(&message, HttpMessage::Request(request) if request.method() == Method::DELETE));
+        assert_eq!(message.to_string(), raw);
+    }
+    #[test]
+    fn from_str_propagates_errors() {
+        let message = "HTTP/1.1 abc\r\n\r\n".parse::<HttpMessage>();
+        assert_eq!(message.unwrap_err(), ParseError::InvalidStatusCode(String::from("abc")));
+        let response = Response::builder().body(Vec::new()).unwrap();
+        assert_eq!(HttpMessage::from(response).to_string(), "HTTP/1.1 200 OK\r\n\r\n");
+    }
+    #[test]
+    fn try_from_bytes_keeps_binary_body() {
+        let raw = b"HTTP/1.1 200 OK\r\ncontent-type:image/png\r\ncontent-length:4\r\n\r\n\x89PNG";
+        let message = HttpMessage::try_from(&raw[..]).unwrap();
+        let HttpMessage::Response(response) = &message else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(response.body(), b"\x89PNG");
+        assert_eq!(message.parser().parse_bytes().unwrap(), raw);
+
+        let raw = b"GET / HTTP/1.1\r\nhost:localhost\r\n\r\n";
+        let message = HttpMessage::try_from(&raw[..]).unwrap();
+        assert!(matches!(message, HttpMessage::Request(request) if request.body().is_empty()));
+    }
+    #[test]
+    fn try_from_bytes_rejects_truncated_buffers() {
+        assert_eq!(
+            HttpMessage::try_from(&b"GET / HTTP/1.1\r\nhost:localhost\r\n"[..]).unwrap_err(),
+            ParseError::MissingHeadTerminator
+        );
+        assert_eq!(
+            HttpMessage::try_from(&b"HTTP/1.1 200 OK\r\ncontent-length:10\r\n\r\nhello"[..]).unwrap_err(),
+            ParseError::ContentLengthMismatch { declared: 10, actual: 5 }
+        );
+        assert_eq!(
+            HttpMessage::try_from(&b"HTTP/1.1 200 OK\r\ncontent-length:abc\r\n\r\n"[..]).unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("content-length"))
+        );
+        assert_eq!(
+            HttpMessage::try_from(&b"GET /\xff HTTP/1.1\r\n\r\n"[..]).unwrap_err(),
+            ParseError::NonAsciiHead
+        );
+    }
+    #[test]
+    fn try_from_bytes_round_trips_not_modified() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "42")
+            .header(header::ETAG, "\"v1\"")
+            .body(vec![b'x'; 42])
+            .unwrap();
+        let bytes = HttpParser::from_response(&response).parse_bytes().unwrap();
+        let HttpMessage::Response(parsed) = HttpMessage::try_from(bytes.as_slice()).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(parsed.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(parsed.headers()[header::CONTENT_LENGTH], "42");
+        assert!(parsed.body().is_empty());
+
+        let framed = HttpParser::from_response(&response).encode_framed().unwrap();
+        assert!(crate::decode_framed(&framed).is_ok());
+    }
+    #[test]
+    fn try_from_bytes_unfolds_obs_fold() {
+        let raw = b"HTTP/1.1 200 OK\r\nx-long:one\r\n\ttwo\r\n\r\n";
+        let HttpMessage::Response(response) = HttpMessage::try_from(&raw[..]).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.headers()["x-long"], "one two");
+    }
+    #[test]
+    fn try_from_bytes_accepts_missing_reason() {
+        for raw in [&b"HTTP/1.1 204\r\n\r\n"[..], &b"HTTP/1.1 204 \r\n\r\n"[..]] {
+            let HttpMessage::Response(response) = HttpMessage::try_from(raw).unwrap() else {
+                panic!("expected a response");
+            };
+            assert_eq!(response.status(), 204);
+        }
+    }
+    #[test]
+    fn eq_ignore_header_order_compares_multisets() {
+        let message = |headers: &[(&str, &str)]| {
+            let mut builder = Response::builder().status(StatusCode::OK);
+            for (name, value) in headers {
+                builder = builder.header(*name, *value);
+            }
+            HttpMessage::from(builder.body(b"hi".to_vec()).unwrap())
+        };
+        let a = message(&[("x-a", "1"), ("set-cookie", "a=1"), ("set-cookie", "b=2")]);
+        let b = message(&[("set-cookie", "b=2"), ("x-a", "1"), ("set-cookie", "a=1")]);
+        assert!(a.eq_ignore_header_order(&b));
+        assert!(!a.eq_ignore_header_order(&message(&[("x-a", "2"), ("set-cookie", "a=1"), ("set-cookie", "b=2")])));
+        assert!(!a.eq_ignore_header_order(&message(&[("x-a", "1"), ("set-cookie", "a=1")])));
+        assert!(!a.eq_ignore_header_order(&message(&[("x-a", "1"), ("set-cookie", "a=1"), ("set-cookie", "a=1")])));
+
+        let request = HttpMessage::from(Request::builder().uri("/").body(b"hi".to_vec()).unwrap());
+        assert!(!a.eq_ignore_header_order(&request));
+    }
+    #[test]
+    fn try_from_decodes_chunked_body() {
+        let raw = b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\nx-id:1\r\n\r\n\
+            5;name=value\r\nhello\r\n7\r\n, world\r\n0\r\nexpires:never\r\n\r\n";
+        let HttpMessage::Response(response) = HttpMessage::try_from(&raw[..]).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.body(), b"hello, world");
+        assert!(!response.headers().contains_key(header::TRANSFER_ENCODING));
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "12");
+        assert_eq!(response.headers()["x-id"], "1");
+
+        let raw = b"POST / HTTP/1.1\r\nhost:x\r\ntransfer-encoding:compress, chunked\r\n\r\n2\r\nhi\r\n0\r\n\r\n";
+        let HttpMessage::Request(request) = HttpMessage::try_from(&raw[..]).unwrap() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.body(), b"hi");
+        assert!(!request.headers().contains_key(header::TRANSFER_ENCODING));
+        assert_eq!(request.headers()[header::CONTENT_ENCODING], "compress");
+        assert_eq!(request.headers()[header::CONTENT_LENGTH], "2");
+    }
+    #[test]
+    fn try_from_rejects_malformed_chunks() {
+        for raw in [
+            &b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\n\r\n5\r\nhel"[..],
+            b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\n\r\nzz\r\nhello\r\n0\r\n\r\n",
+            b"HTTP/1.1 200 OK\r\ntransfer-encoding:chunked\r\n\r\n5\r\nhello\r\n0\r\n",
+        ] {
+            assert!(matches!(HttpMessage::try_from(raw), Err(ParseError::InvalidMessage(_))), "{raw:?}");
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{uri_form, HttpParser, Message, ParseError};
+use http::{HeaderName, Method};
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+const X_HTTP_METHOD_OVERRIDE: HeaderName = HeaderName::from_static("x-http-method-override");
+const KNOWN_METHODS: [Method; 9] = [
+    Method::GET,
+    Method::HEAD,
+    Method::POST,
+    Method::PUT,
+    Method::DELETE,
+    Method::CONNECT,
+    Method::OPTIONS,
+    Method::TRACE,
+    Method::PATCH,
+];
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes a request with the method from `X-HTTP-Method-Override` in the request line,
+    /// as a server honoring the override would see it. The header itself is kept. Without the
+    /// header this is `parse()`; an override that is not a standard method is `InvalidMethod`.
+    pub fn parse_method_override(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let Some(value) = request.headers().get(X_HTTP_METHOD_OVERRIDE) else {
+            return self.parse();
+        };
+        let value = String::from_utf8_lossy(value.as_bytes());
+        let method = KNOWN_METHODS.into_iter()
+            .find(|method| method.as_str() == value.trim())
+            .ok_or_else(|| ParseError::InvalidMethod(value.to_string()))?;
+        let target = uri_form::default_target(&method, request.uri());
+        let head = self.request_head_with(&method, &target, request.headers())?;
+        Ok(Self::join_head(head, &self.body_string()))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{Method, Request, Version};
+
+    fn request(method_override: &str) -> Request<&'static str> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/items/7")
+            .version(Version::HTTP_11)
+            .header("x-http-method-override", method_override)
+            .body("")
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_method_override_rewrites_request_line() {
+        let delete = request("DELETE");
+        let parser = HttpParser::from_request(&delete);
+        assert_eq!(
+            parser.parse().unwrap(),
+            "POST /items/7 HTTP/1.1\r\nx-http-method-override:DELETE\r\n\r\n"
+        );
+        assert_eq!(
+            parser.parse_method_override().unwrap(),
+            "DELETE /items/7 HTTP/1.1\r\nx-http-method-override:DELETE\r\n\r\n"
+        );
+
+        let unknown = request("PURGE");
+        assert_eq!(
+            HttpParser::from_request(&unknown).parse_method_override().unwrap_err(),
+            ParseError::InvalidMethod(String::from("PURGE"))
+        );
+        let plain = Request::builder().method(Method::PUT).uri("/").body("").unwrap();
+        let parser = HttpParser::from_request(&plain);
+        assert_eq!(parser.parse_method_override(), parser.parse());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::ParseError;
+use http::{header, HeaderName, HeaderValue};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Encodes `fields` as a `multipart/form-data` body (RFC 7578) and returns the matching
+/// `Content-Type` header. Values that are not UTF-8 are labelled `application/octet-stream`.
+pub fn multipart_body(
+    fields: &[(String, Vec<u8>)],
+    boundary: &str,
+) -> Result<(HeaderName, HeaderValue, Vec<u8>), ParseError> {
+    let content_type = HeaderValue::from_str(&format!("multipart/form-data; boundary={boundary}"))
+        .ok()
+        .filter(|_| (1..=70).contains(&boundary.len()))
+        .ok_or_else(|| ParseError::InvalidHeaderValue(header::CONTENT_TYPE.to_string()))?;
+    let delimiter = format!("--{boundary}");
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        if value.windows(delimiter.len()).any(|window| window == delimiter.as_bytes()) {
+            return Err(ParseError::InvalidMessage(format!("field {name:?} contains the boundary")));
+        }
+        let name = name.replace('"', "%22").replace(['\r', '\n'], " ");
+        body.extend_from_slice(format!("{delimiter}\r\n").as_bytes());
+        body.extend_from_slice(format!("Content-Disposition: form-data; name=\"{name}\"\r\n").as_bytes());
+        if core::str::from_utf8(value).is_err() {
+            body.extend_from_slice(b"Content-Type: application/octet-stream\r\n");
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(value);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("{delimiter}--\r\n").as_bytes());
+    Ok((header::CONTENT_TYPE, content_type, body))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{multipart_body, HttpParser, ParseError};
+    use http::{header, Method, Request};
+
+    #[test]
+    fn multipart_body_encodes_fields() {
+        let fields = [
+            (String::from("title"), b"hello".to_vec()),
+            (String::from("file"), vec![0x00, 0xff, 0x10]),
+        ];
+        let (name, value, body) = multipart_body(&fields, "XyZ123").unwrap();
+        assert_eq!(name, header::CONTENT_TYPE);
+        assert_eq!(value, "multipart/form-data; boundary=XyZ123");
+        let mut expected = b"--XyZ123\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nhello\r\n\
+            --XyZ123\r\nContent-Disposition: form-data; name=\"file\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+            .to_vec();
+        expected.extend_from_slice(&[0x00, 0xff, 0x10]);
+        expected.extend_from_slice(b"\r\n--XyZ123--\r\n");
+        assert_eq!(body, expected);
+
+        let request = Request::builder().method(Method::POST).uri("/upload").header(name, value).body(body).unwrap();
+        let bytes = HttpParser::from_request(&request).parse_bytes().unwrap();
+        assert!(bytes.starts_with(b"POST /upload HTTP/1.1\r\ncontent-type:multipart/form-data; boundary=XyZ123\r\n\r\n--XyZ123\r\n"));
+    }
+    #[test]
+    fn multipart_body_rejects_bad_boundaries() {
+        let fields = [(String::from("text"), b"a --sep b".to_vec())];
+        assert!(matches!(multipart_body(&fields, "sep"), Err(ParseError::InvalidMessage(_))));
+        assert_eq!(
+            multipart_body(&[], "").unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("content-type"))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{chunked, decode, HttpParser, ParseError};
+use http::{header, Request};
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+/// Serializes requests back-to-back for HTTP/1.1 pipelining. Each body must be framed by a
+/// matching `Content-Length` or be a complete chunked encoding so the receiver can find the next
+/// request; a request with both headers is `ConflictingFraming`.
+pub fn serialize_pipeline<T: Display>(requests: &[Request<T>]) -> Result<String, ParseError> {
+    let mut pipeline = String::new();
+    for (index, request) in requests.iter().enumerate() {
+        let parser = HttpParser::from_request(request);
+        parser.check_framing()?;
+        let body = request.body().to_string();
+        let headers = request.headers();
+        if chunked::is_chunked(headers) {
+            match chunked::decode_chunked_prefix(body.as_bytes()) {
+                Ok(Some((_, used))) if used == body.len() => {}
+                _ => return Err(ParseError::UnframedBody(index)),
+            }
+        } else if headers.contains_key(header::CONTENT_LENGTH) {
+            decode::check_declared_length(headers, body.len())?;
+        } else if !body.is_empty() {
+            return Err(ParseError::UnframedBody(index));
+        }
+        pipeline.push_str(&parser.parse()?);
+    }
+    Ok(pipeline)
+}
+/// Serializes each message only when the iterator reaches it, like `parse_bytes()` with the
+/// output read as UTF-8 (lossily, as `HttpMessage`'s `Display` does).
+pub fn serialize_all<'a, T, I>(messages: I) -> impl Iterator<Item = Result<String, ParseError>>
+where
+    T: AsRef<[u8]> + 'a,
+    I: IntoIterator<Item = HttpParser<'a, T>>,
+{
+    messages.into_iter()
+        .map(|parser| parser.parse_bytes().map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{serialize_all, serialize_pipeline, HttpMessage, HttpParser, ParseError};
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    fn request(path: &str, body: &'static str, length: Option<&str>) -> Request<&'static str> {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(path)
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost");
+        if let Some(length) = length {
+            request = request.header(header::CONTENT_LENGTH, length);
+        }
+        request.body(body).unwrap()
+    }
+
+    #[test]
+    fn serialize_pipeline_concatenates_requests() {
+        let requests = [request("/a", "one", Some("3")), request("/b", "second", Some("6"))];
+        assert_eq!(
+            serialize_pipeline(&requests).unwrap(),
+            "POST /a HTTP/1.1\r\nhost:localhost\r\ncontent-length:3\r\n\r\none\
+             POST /b HTTP/1.1\r\nhost:localhost\r\ncontent-length:6\r\n\r\nsecond"
+        );
+        let bodyless = [request("/a", "", None), request("/b", "", None)];
+        let expected = HttpParser::from_request(&bodyless[0]).parse().unwrap()
+            + &HttpParser::from_request(&bodyless[1]).parse().unwrap();
+        assert_eq!(serialize_pipeline(&bodyless).unwrap(), expected);
+    }
+    #[test]
+    fn serialize_pipeline_rejects_ambiguous_framing() {
+        let requests = [request("/a", "one", Some("3")), request("/b", "two", None), request("/c", "", None)];
+        assert_eq!(serialize_pipeline(&requests).unwrap_err(), ParseError::UnframedBody(1));
+
+        let requests = [request("/a", "one", Some("5")), request("/b", "", None)];
+        assert_eq!(
+            serialize_pipeline(&requests).unwrap_err(),
+            ParseError::ContentLengthMismatch { declared: 5, actual: 3 }
+        );
+
+        let chunked = |body: &'static str| {
+            let mut request = request("/b", body, None);
+            request.headers_mut().insert(header::TRANSFER_ENCODING, "chunked".parse().unwrap());
+            request
+        };
+        let requests = [request("/a", "one", Some("3")), chunked("3\r\ntwo\r\n0\r\n\r\n"), request("/c", "", None)];
+        assert!(serialize_pipeline(&requests).unwrap().contains("\r\n\r\n3\r\ntwo\r\n0\r\n\r\nPOST /c"));
+        for body in ["hello", "3\r\ntwo\r\n", "3\r\ntwo\r\n0\r\n\r\nextra"] {
+            let requests = [request("/a", "one", Some("3")), chunked(body), request("/c", "", None)];
+            assert_eq!(serialize_pipeline(&requests).unwrap_err(), ParseError::UnframedBody(1));
+        }
+
+        let mut both = chunked("3\r\ntwo\r\n0\r\n\r\n");
+        both.headers_mut().insert(header::CONTENT_LENGTH, "15".parse().unwrap());
+        let requests = [request("/a", "one", Some("3")), both, request("/c", "", None)];
+        assert_eq!(serialize_pipeline(&requests).unwrap_err(), ParseError::ConflictingFraming);
+    }
+    #[test]
+    fn serialize_all_yields_messages_in_order() {
+        let messages = [
+            HttpMessage::Request(Request::builder().uri("/a").body(Vec::new()).unwrap()),
+            HttpMessage::Response(Response::builder().status(StatusCode::OK).body(b"ok".to_vec()).unwrap()),
+            HttpMessage::Request(Request::builder().method(Method::DELETE).uri("/b").body(Vec::new()).unwrap()),
+        ];
+        let mut serialized = serialize_all(messages.iter().map(HttpMessage::parser));
+        assert_eq!(serialized.next().unwrap().unwrap(), "GET /a HTTP/1.1\r\n\r\n");
+        assert_eq!(serialized.next().unwrap().unwrap(), "HTTP/1.1 200 OK\r\n\r\nok");
+        assert_eq!(serialized.next().unwrap().unwrap(), "DELETE /b HTTP/1.1\r\n\r\n");
+        assert!(serialized.next().is_none());
+    }
+}
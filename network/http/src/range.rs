@@ -0,0 +1,127 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderValue, Response, StatusCode};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+impl<T: Display> HttpParser<'_, T> {
+    /// Serializes a request after checking that its `Range` header is a valid RFC 7233
+    /// byte-range set. The header is rewritten without optional whitespace (`bytes=0-99,200-`).
+    pub fn parse_request_validated_range(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        if !request.headers().contains_key(header::RANGE) {
+            return self.parse();
+        }
+        let mut headers = request.headers().clone();
+        if let header::Entry::Occupied(mut entry) = headers.entry(header::RANGE) {
+            for value in entry.iter_mut() {
+                let invalid = || ParseError::InvalidRange(String::from_utf8_lossy(value.as_bytes()).into_owned());
+                let range = value.to_str().ok().and_then(normalize_range).ok_or_else(invalid)?;
+                *value = HeaderValue::from_str(&range)
+                    .map_err(|_| ParseError::InvalidRange(range))?;
+            }
+        }
+        self.parse_with_headers(&headers)
+    }
+}
+/// A `206 Partial Content` response carrying `body`, the bytes `start..=end` of a `total`-byte
+/// representation, with `Content-Range`, `Content-Length` and `Accept-Ranges` set.
+pub fn partial_content_response(body: &[u8], range: (u64, u64), total: u64) -> Result<Response<Vec<u8>>, ParseError> {
+    let (start, end) = range;
+    let content_range = format!("bytes {start}-{end}/{total}");
+    if start > end || end >= total || end - start + 1 != body.len() as u64 {
+        return Err(ParseError::InvalidRange(content_range));
+    }
+    let content_range = HeaderValue::from_str(&content_range)
+        .map_err(|_| ParseError::InvalidRange(content_range))?;
+    let mut response = Response::new(body.to_vec());
+    *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+    let headers = response.headers_mut();
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    headers.insert(header::CONTENT_RANGE, content_range);
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+    Ok(response)
+}
+/// Parses `bytes=first-last, first-, -suffix` and re-emits it canonically.
+fn normalize_range(value: &str) -> Option<String> {
+    let (unit, set) = value.trim().split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let specs = set.split(',')
+        .map(|spec| {
+            let (first, last) = spec.trim().split_once('-')?;
+            match (first.trim().is_empty(), last.trim().is_empty()) {
+                (true, true) => None,
+                (true, false) => Some(format!("-{}", position(last)?)),
+                (false, true) => Some(format!("{}-", position(first)?)),
+                (false, false) => {
+                    let (first, last) = (position(first)?, position(last)?);
+                    (first <= last).then(|| format!("{first}-{last}"))
+                }
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("bytes={}", specs.join(",")))
+}
+/// A byte position: ASCII digits only, so `+1` and `0x10` are rejected.
+fn position(digits: &str) -> Option<u64> {
+    let digits = digits.trim();
+    if digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::normalize_range;
+    use crate::{partial_content_response, HttpParser, ParseError};
+    use http::{header, Request};
+
+    fn request(range: &str) -> Request<&'static str> {
+        Request::builder().uri("/video.mp4").header(header::RANGE, range).body("").unwrap()
+    }
+
+    #[test]
+    fn normalize_range_accepts_byte_range_sets() {
+        assert_eq!(normalize_range("bytes=0-1023").as_deref(), Some("bytes=0-1023"));
+        assert_eq!(normalize_range("bytes= 0-99 , 200-299").as_deref(), Some("bytes=0-99,200-299"));
+        assert_eq!(normalize_range("bytes=-500").as_deref(), Some("bytes=-500"));
+        assert_eq!(normalize_range("Bytes=9500-").as_deref(), Some("bytes=9500-"));
+        for invalid in ["bytes=100-50", "bytes=-", "bytes=a-b", "bytes=1-2-3", "items=0-1", "bytes=+1-2", "0-1", "bytes="] {
+            assert_eq!(normalize_range(invalid), None, "{invalid:?}");
+        }
+    }
+    #[test]
+    fn parse_request_validated_range() {
+        let valid = request("bytes=0-99, 200-299");
+        assert_eq!(
+            HttpParser::from_request(&valid).parse_request_validated_range().unwrap(),
+            "GET /video.mp4 HTTP/1.1\r\nrange:bytes=0-99,200-299\r\n\r\n"
+        );
+        let reversed = request("bytes=100-50");
+        assert_eq!(
+            HttpParser::from_request(&reversed).parse_request_validated_range().unwrap_err(),
+            ParseError::InvalidRange(String::from("bytes=100-50"))
+        );
+    }
+    #[test]
+    fn partial_content_response_sets_content_range() {
+        let response = partial_content_response(b"world", (6, 10), 11).unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse_bytes().unwrap(),
+            b"HTTP/1.1 206 Partial Content\r\naccept-ranges:bytes\r\ncontent-range:bytes 6-10/11\r\n\
+              content-length:5\r\n\r\nworld"
+        );
+        assert_eq!(
+            partial_content_response(b"world", (7, 11), 11).unwrap_err(),
+            ParseError::InvalidRange(String::from("bytes 7-11/11"))
+        );
+        assert!(partial_content_response(b"world", (6, 9), 11).is_err());
+        assert!(partial_content_response(b"", (1, 0), 11).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{HttpMessage, ParseError};
+use alloc::vec::Vec;
+
+/// A parsed message that keeps the exact bytes it was parsed from, for proxies that must
+/// forward a message unchanged while still inspecting it.
+#[derive(Clone, Debug)]
+pub struct RawMessage {
+    raw: Vec<u8>,
+    head_len: usize,
+    message: HttpMessage,
+}
+impl RawMessage {
+    /// Parses `raw` as `HttpMessage::try_from` does and keeps a copy of the input.
+    pub fn parse(raw: &[u8]) -> Result<Self, ParseError> {
+        let message = HttpMessage::try_from(raw)?;
+        let head_len = raw.windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .map_or(raw.len(), |index| index + 4);
+        Ok(RawMessage { raw: raw.to_vec(), head_len, message })
+    }
+    /// The parsed view; header names are lowercased and values trimmed here, unlike in `head()`.
+    pub fn message(&self) -> &HttpMessage {
+        &self.message
+    }
+    /// The original start line and header bytes, including the blank line.
+    pub fn head(&self) -> &[u8] {
+        &self.raw[..self.head_len]
+    }
+    /// The original body bytes, still transfer-encoded if the message was chunked.
+    pub fn raw_body(&self) -> &[u8] {
+        &self.raw[self.head_len..]
+    }
+    /// The input, byte for byte.
+    pub fn reserialize(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpMessage, RawMessage};
+
+    #[test]
+    fn raw_message_round_trips_byte_for_byte() {
+        let raw = b"POST /submit HTTP/1.1\r\nHoSt:   example.com  \r\nX-Odd:\tvalue\twith tabs\r\ncontent-LENGTH: 2\r\n\r\nhi";
+        let message = RawMessage::parse(raw).unwrap();
+        assert_eq!(message.reserialize(), raw);
+        assert_eq!(message.raw_body(), b"hi");
+        assert!(message.head().ends_with(b"content-LENGTH: 2\r\n\r\n"));
+        let HttpMessage::Request(request) = message.message() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.headers()["host"], "example.com");
+        assert_eq!(request.headers()["x-odd"], "value\twith tabs");
+        assert_ne!(message.message().to_string().as_bytes(), raw);
+
+        assert!(RawMessage::parse(b"GET / HTTP/1.1\r\n").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use crate::ParseError;
+use http::{header, HeaderValue, Response, StatusCode};
+use alloc::string::{String, ToString};
+
+/// A body-less redirect to `location`. `status` must be a 3xx code.
+pub fn redirect_response(status: StatusCode, location: &str) -> Result<Response<String>, ParseError> {
+    if !status.is_redirection() {
+        return Err(ParseError::InvalidStatusCode(status.as_str().to_string()));
+    }
+    let location = HeaderValue::from_str(location)
+        .map_err(|_| ParseError::InvalidHeaderValue(header::LOCATION.to_string()))?;
+    let mut response = Response::new(String::new());
+    *response.status_mut() = status;
+    response.headers_mut().insert(header::LOCATION, location);
+    Ok(response)
+}
+#[cfg(test)]
+mod tests {
+    use crate::{redirect_response, HttpParser, ParseError};
+    use http::StatusCode;
+
+    #[test]
+    fn redirect_response_sets_location() {
+        let response = redirect_response(StatusCode::FOUND, "https://example.com/new").unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 302 Found\r\nlocation:https://example.com/new\r\n\r\n"
+        );
+        assert_eq!(
+            redirect_response(StatusCode::OK, "/").unwrap_err(),
+            ParseError::InvalidStatusCode(String::from("200"))
+        );
+        assert_eq!(
+            redirect_response(StatusCode::MOVED_PERMANENTLY, "/a\r\nSet-Cookie: x=1").unwrap_err(),
+            ParseError::InvalidHeaderValue(String::from("location"))
+        );
+    }
+}
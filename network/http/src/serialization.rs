@@ -0,0 +1,160 @@
+use crate::{version_from_str, version_str, HttpMessage, ParseError};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri, Version};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The JSON shape of a captured message: headers as an ordered list so duplicates survive,
+/// and the body as base64 so binary payloads do too.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Record {
+    Request {
+        method: String,
+        uri: String,
+        version: String,
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+    Response {
+        status: u16,
+        version: String,
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+}
+impl Serialize for HttpMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let record = match self {
+            HttpMessage::Request(request) => Record::Request {
+                method: request.method().to_string(),
+                uri: request.uri().to_string(),
+                version: encode_version(request.version()).map_err(ser::Error::custom)?,
+                headers: encode_headers(request.headers()).map_err(ser::Error::custom)?,
+                body: STANDARD.encode(request.body()),
+            },
+            HttpMessage::Response(response) => Record::Response {
+                status: response.status().as_u16(),
+                version: encode_version(response.version()).map_err(ser::Error::custom)?,
+                headers: encode_headers(response.headers()).map_err(ser::Error::custom)?,
+                body: STANDARD.encode(response.body()),
+            },
+        };
+        record.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for HttpMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let message = match Record::deserialize(deserializer)? {
+            Record::Request { method, uri, version, headers, body } => {
+                let mut request = Request::new(decode_body(&body).map_err(de::Error::custom)?);
+                *request.method_mut() = Method::from_bytes(method.as_bytes())
+                    .map_err(|_| de::Error::custom(ParseError::InvalidMethod(method)))?;
+                *request.uri_mut() = uri.parse::<Uri>()
+                    .map_err(|_| de::Error::custom(ParseError::InvalidUri(uri)))?;
+                *request.version_mut() = decode_version(version).map_err(de::Error::custom)?;
+                *request.headers_mut() = decode_headers(headers).map_err(de::Error::custom)?;
+                HttpMessage::Request(request)
+            }
+            Record::Response { status, version, headers, body } => {
+                let mut response = Response::new(decode_body(&body).map_err(de::Error::custom)?);
+                *response.status_mut() = StatusCode::from_u16(status)
+                    .map_err(|_| de::Error::custom(ParseError::InvalidStatusCode(status.to_string())))?;
+                *response.version_mut() = decode_version(version).map_err(de::Error::custom)?;
+                *response.headers_mut() = decode_headers(headers).map_err(de::Error::custom)?;
+                HttpMessage::Response(response)
+            }
+        };
+        Ok(message)
+    }
+}
+fn encode_version(version: Version) -> Result<String, ParseError> {
+    version_str(version)
+        .map(String::from)
+        .ok_or(ParseError::UnsupportedVersion(version))
+}
+fn decode_version(version: String) -> Result<Version, ParseError> {
+    version_from_str(&version).ok_or(ParseError::InvalidVersionToken(version))
+}
+fn encode_headers(headers: &HeaderMap) -> Result<Vec<(String, String)>, ParseError> {
+    headers.iter()
+        .map(|(name, value)| {
+            let value = value.to_str()
+                .map_err(|_| ParseError::InvalidHeaderValue(name.to_string()))?;
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+fn decode_headers(headers: Vec<(String, String)>) -> Result<HeaderMap, ParseError> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| ParseError::InvalidHeaderName(name))?;
+        let value = HeaderValue::from_str(&value)
+            .map_err(|_| ParseError::InvalidHeaderValue(name.to_string()))?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+fn decode_body(body: &str) -> Result<Vec<u8>, ParseError> {
+    STANDARD.decode(body).map_err(|error| ParseError::InvalidMessage(error.to_string()))
+}
+#[cfg(test)]
+mod tests {
+    use crate::HttpMessage;
+    use http::{header, Method, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn request_round_trips_through_json() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/upload?id=1")
+            .version(Version::HTTP_11)
+            .header(header::ACCEPT, "text/html")
+            .header(header::ACCEPT, "text/plain")
+            .body(vec![0x00, 0xff, 0x89, b'P', b'N', b'G'])
+            .unwrap();
+        let message = HttpMessage::from(request);
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains(r#""version":"HTTP/1.1""#));
+        assert!(json.contains(r#""headers":[["accept","text/html"],["accept","text/plain"]]"#));
+        assert!(json.contains(r#""body":"AP+JUE5H""#));
+
+        let HttpMessage::Request(parsed) = serde_json::from_str::<HttpMessage>(&json).unwrap() else {
+            panic!("expected a request");
+        };
+        let HttpMessage::Request(request) = &message else {
+            unreachable!();
+        };
+        assert_eq!(parsed.method(), request.method());
+        assert_eq!(parsed.uri(), request.uri());
+        assert_eq!(parsed.version(), request.version());
+        assert_eq!(parsed.headers(), request.headers());
+        assert_eq!(parsed.body(), request.body());
+    }
+    #[test]
+    fn response_round_trips_through_json() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .version(Version::HTTP_2)
+            .header(header::SET_COOKIE, "a=1")
+            .header(header::SET_COOKIE, "b=2")
+            .body(b"missing".to_vec())
+            .unwrap();
+        let message = HttpMessage::from(response);
+        let json = serde_json::to_string(&message).unwrap();
+        let parsed = serde_json::from_str::<HttpMessage>(&json).unwrap();
+        assert_eq!(parsed.to_string(), message.to_string());
+    }
+    #[test]
+    fn deserialize_rejects_invalid_fields() {
+        let json = r#"{"kind":"response","status":200,"version":"HTTP/1.11","headers":[],"body":""}"#;
+        let error = serde_json::from_str::<HttpMessage>(json).unwrap_err();
+        assert!(error.to_string().contains("invalid version token"));
+
+        let json = r#"{"kind":"response","status":200,"version":"HTTP/1.1","headers":[],"body":"***"}"#;
+        assert!(serde_json::from_str::<HttpMessage>(json).is_err());
+    }
+}
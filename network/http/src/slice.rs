@@ -0,0 +1,72 @@
+use crate::{HttpParser, ParseError, WireWrite};
+use core::fmt;
+
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// Writes the `parse_bytes()` output into `buf` without allocating, returning its length.
+    /// If it doesn't fit, `buf` holds as much as fit and the error reports the size needed.
+    pub fn write_to_slice(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.parse_version()?;
+        let mut out = SliceWriter { buf, needed: 0 };
+        // `SliceWriter` never fails and the version was checked above, so this cannot error.
+        let _ = self.write_head_into(self.headers(), &mut out);
+        out.put(self.body_bytes());
+        if out.needed > out.buf.len() {
+            return Err(ParseError::BufferTooSmall { needed: out.needed });
+        }
+        Ok(out.needed)
+    }
+}
+/// Copies into a fixed buffer, dropping what doesn't fit but counting every byte.
+struct SliceWriter<'b> {
+    buf: &'b mut [u8],
+    needed: usize,
+}
+impl SliceWriter<'_> {
+    fn put(&mut self, bytes: &[u8]) {
+        let start = self.needed.min(self.buf.len());
+        let fit = bytes.len().min(self.buf.len() - start);
+        self.buf[start..start + fit].copy_from_slice(&bytes[..fit]);
+        self.needed += bytes.len();
+    }
+}
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.put(s.as_bytes());
+        Ok(())
+    }
+}
+impl WireWrite for SliceWriter<'_> {
+    fn write_value(&mut self, value: &[u8]) -> fmt::Result {
+        self.put(value);
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, HeaderValue, Response, StatusCode, Version};
+
+    #[test]
+    fn write_to_slice_reports_needed_size() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "5")
+            .header("x-raw", HeaderValue::from_bytes(b"caf\xe9").unwrap())
+            .body(b"hello".to_vec())
+            .unwrap();
+        let parser = HttpParser::from_response(&response);
+        let expected = parser.parse_bytes().unwrap();
+
+        let mut buf = vec![0; expected.len()];
+        assert_eq!(parser.write_to_slice(&mut buf).unwrap(), expected.len());
+        assert_eq!(buf, expected);
+
+        let mut short = vec![0; expected.len() - 1];
+        assert_eq!(
+            parser.write_to_slice(&mut short).unwrap_err(),
+            ParseError::BufferTooSmall { needed: expected.len() }
+        );
+        assert_eq!(short, expected[..expected.len() - 1]);
+    }
+}
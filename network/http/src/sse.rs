@@ -0,0 +1,66 @@
+use http::HeaderValue;
+use alloc::string::String;
+
+/// One server-sent event. `data` may span several lines; `event` and `id` must be single-line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+}
+/// Formats `events` as a `text/event-stream` body and returns the matching `Content-Type` value.
+/// Each line of `data` becomes its own `data:` field; events are separated by a blank line.
+pub fn sse_body(events: &[SseEvent]) -> (HeaderValue, String) {
+    let mut body = String::new();
+    for event in events {
+        let fields = [("event", &event.event), ("id", &event.id)];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                push_field(&mut body, field, &value.replace(['\r', '\n'], " "));
+            }
+        }
+        for line in event.data.split('\n') {
+            push_field(&mut body, "data", line.strip_suffix('\r').unwrap_or(line));
+        }
+        body.push('\n');
+    }
+    (HeaderValue::from_static("text/event-stream"), body)
+}
+fn push_field(body: &mut String, field: &str, value: &str) {
+    body.push_str(field);
+    body.push_str(": ");
+    body.push_str(value);
+    body.push('\n');
+}
+#[cfg(test)]
+mod tests {
+    use crate::{sse_body, HttpParser, SseEvent};
+    use http::{header, Response, StatusCode};
+
+    #[test]
+    fn sse_body_splits_multi_line_data() {
+        let events = [
+            SseEvent {
+                event: Some(String::from("update")),
+                id: Some(String::from("42")),
+                data: String::from("first line\nsecond line"),
+            },
+            SseEvent {
+                data: String::from("ping"),
+                ..SseEvent::default()
+            },
+        ];
+        let (content_type, body) = sse_body(&events);
+        assert_eq!(content_type, "text/event-stream");
+        assert_eq!(body, "event: update\nid: 42\ndata: first line\ndata: second line\n\ndata: ping\n\n");
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body)
+            .unwrap();
+        assert!(HttpParser::from_response(&response).parse().unwrap().starts_with(
+            "HTTP/1.1 200 OK\r\ncontent-type:text/event-stream\r\n\r\nevent: update\n"
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{HttpParser, Message, ParseError};
+use alloc::string::ToString;
+
+/// The class of a status code, from its first digit (RFC 9110 §15).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusClass {
+    Informational,
+    Success,
+    Redirection,
+    ClientError,
+    ServerError,
+}
+impl<T> HttpParser<'_, T> {
+    /// The response's status class; codes from 600 up, which `http` allows, have none.
+    pub fn status_class(&self) -> Result<StatusClass, ParseError> {
+        let Message::Response(response) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        match response.status().as_u16() / 100 {
+            1 => Ok(StatusClass::Informational),
+            2 => Ok(StatusClass::Success),
+            3 => Ok(StatusClass::Redirection),
+            4 => Ok(StatusClass::ClientError),
+            5 => Ok(StatusClass::ServerError),
+            _ => Err(ParseError::InvalidStatusCode(response.status().as_str().to_string())),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError, StatusClass};
+    use http::{Request, Response};
+
+    #[test]
+    fn status_class_follows_first_digit() {
+        let class = |code: u16| HttpParser::from_response(&Response::builder().status(code).body("").unwrap()).status_class();
+        let boundaries = [
+            (100, StatusClass::Informational),
+            (199, StatusClass::Informational),
+            (200, StatusClass::Success),
+            (299, StatusClass::Success),
+            (300, StatusClass::Redirection),
+            (399, StatusClass::Redirection),
+            (400, StatusClass::ClientError),
+            (499, StatusClass::ClientError),
+            (500, StatusClass::ServerError),
+            (599, StatusClass::ServerError),
+        ];
+        for (code, expected) in boundaries {
+            assert_eq!(class(code), Ok(expected), "{code}");
+        }
+        assert_eq!(class(600), Err(ParseError::InvalidStatusCode(String::from("600"))));
+
+        let request = Request::builder().uri("/").body("").unwrap();
+        assert_eq!(HttpParser::from_request(&request).status_class(), Err(ParseError::WrongVariant));
+    }
+}
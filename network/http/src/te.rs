@@ -0,0 +1,59 @@
+use crate::ParseError;
+use http::{header, HeaderValue};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+const TRANSFER_CODINGS: [&str; 6] = ["trailers", "gzip", "x-gzip", "deflate", "compress", "x-compress"];
+
+/// A request `TE` value (RFC 9110 §10.1.4) such as `trailers, deflate;q=0.5`. A weight of 1 is
+/// left implicit and others are written with up to three decimals. `trailers` takes no weight,
+/// and `chunked` is always accepted so it may not be listed.
+pub fn te_header(codings: &[(&str, f32)]) -> Result<HeaderValue, ParseError> {
+    let invalid = || ParseError::InvalidHeaderValue(header::TE.to_string());
+    let mut items = Vec::with_capacity(codings.len());
+    for &(coding, weight) in codings {
+        let coding = coding.to_ascii_lowercase();
+        if !TRANSFER_CODINGS.contains(&coding.as_str()) || !(0.0..=1.0).contains(&weight) {
+            return Err(invalid());
+        }
+        let millis = (weight * 1000.0 + 0.5) as u16;
+        match millis {
+            1000 => items.push(coding),
+            _ if coding == "trailers" => return Err(invalid()),
+            0 => items.push(format!("{coding};q=0")),
+            _ => items.push(format!("{coding};q={}", format!("0.{millis:03}").trim_end_matches('0'))),
+        }
+    }
+    HeaderValue::from_str(&items.join(", ")).map_err(|_| invalid())
+}
+#[cfg(test)]
+mod tests {
+    use crate::{te_header, HttpParser, ParseError};
+    use http::{header, Request, Version};
+
+    #[test]
+    fn te_header_formats_weights() {
+        let te = te_header(&[("trailers", 1.0), ("deflate", 0.5), ("gzip", 0.125), ("compress", 0.0)]).unwrap();
+        assert_eq!(te, "trailers, deflate;q=0.5, gzip;q=0.125, compress;q=0");
+        let request = Request::builder()
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::TE, te_header(&[("trailers", 1.0), ("deflate", 0.5)]).unwrap())
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse().unwrap(),
+            "GET / HTTP/1.1\r\nte:trailers, deflate;q=0.5\r\n\r\n"
+        );
+    }
+    #[test]
+    fn te_header_rejects_invalid_codings() {
+        let invalid = ParseError::InvalidHeaderValue(String::from("te"));
+        assert_eq!(te_header(&[("chunked", 1.0)]).unwrap_err(), invalid);
+        assert_eq!(te_header(&[("br", 1.0)]).unwrap_err(), invalid);
+        assert_eq!(te_header(&[("gzip", 1.5)]).unwrap_err(), invalid);
+        assert_eq!(te_header(&[("trailers", 0.5)]).unwrap_err(), invalid);
+        assert_eq!(te_header(&[("gzip", f32::NAN)]).unwrap_err(), invalid);
+    }
+}
@@ -0,0 +1,55 @@
+use http::{header, HeaderMap, HeaderName, HeaderValue, Response, StatusCode};
+use alloc::string::String;
+
+/// A canned response for test servers, turned into a real `Response` for `HttpParser`.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseTemplate {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+impl ResponseTemplate {
+    pub fn new(status: StatusCode) -> Self {
+        ResponseTemplate { status, ..Self::default() }
+    }
+    /// Appends a header, so repeated names (e.g. `Set-Cookie`) are all kept.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+    /// Sets an already-encoded JSON body and `Content-Type: application/json`.
+    pub fn with_json(mut self, json: impl Into<String>) -> Self {
+        self.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.with_body(json)
+    }
+    pub fn into_response(self) -> Response<String> {
+        let mut response = Response::new(self.body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ResponseTemplate};
+    use http::{header, HeaderValue, StatusCode};
+
+    #[test]
+    fn template_serializes_as_expected() {
+        let response = ResponseTemplate::new(StatusCode::CREATED)
+            .with_header(header::LOCATION, HeaderValue::from_static("/users/7"))
+            .with_json(r#"{"id":7}"#)
+            .into_response();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 201 Created\r\nlocation:/users/7\r\ncontent-type:application/json\r\n\r\n{\"id\":7}"
+        );
+
+        let response = ResponseTemplate::default().with_body("ok").into_response();
+        assert_eq!(HttpParser::from_response(&response).parse().unwrap(), "HTTP/1.1 200 OK\r\n\r\nok");
+    }
+}
@@ -0,0 +1,360 @@
+use crate::{HttpParser, Message, ParseError};
+use http::{header, HeaderValue, Method, Uri};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::borrow::Cow;
+use core::fmt::{self, Display};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UriForm {
+    /// `GET /path?query HTTP/1.1`, with the authority moved into a `Host` header.
+    #[default]
+    Origin,
+    /// `GET http://host/path?query HTTP/1.1`, as sent to a forward proxy.
+    Absolute,
+}
+impl<T: Display> HttpParser<'_, T> {
+    pub fn parse_request_form(&self, form: UriForm) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let uri = request.uri();
+        let mut headers = Cow::Borrowed(request.headers());
+        let target = match form {
+            UriForm::Origin => {
+                if !headers.contains_key(header::HOST) && let Some(host) = host_of(uri) {
+                    let host = HeaderValue::from_str(host)
+                        .map_err(|_| ParseError::InvalidHeaderValue(header::HOST.to_string()))?;
+                    headers.to_mut().insert(header::HOST, host);
+                }
+                origin_form(uri)
+            }
+            UriForm::Absolute => uri.to_string(),
+        };
+        let target = match fixed_target(request.method(), uri) {
+            Some(authority) => authority.to_string(),
+            None => target,
+        };
+        let head = self.request_head_with(request.method(), &target, &headers)?;
+        Ok(Self::join_head(head, &self.body_string()))
+    }
+    /// Serializes a request with its path and query percent-encoded per RFC 3986.
+    /// Existing `%XX` escapes are kept as they are rather than encoded twice.
+    pub fn parse_request_normalized(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let target = match fixed_target(request.method(), request.uri()) {
+            Some(authority) => authority.to_string(),
+            None => normalized_target(request.uri()),
+        };
+        let head = self.request_head_with(request.method(), &target, request.headers())?;
+        Ok(Self::join_head(head, &self.body_string()))
+    }
+    /// Serializes a request with its query pairs sorted by key, then value, as request-signing
+    /// schemes such as AWS SigV4 expect. Duplicate keys and keys without a value are kept.
+    pub fn parse_request_canonical_query(&self) -> Result<String, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let uri = request.uri();
+        let target = match (fixed_target(request.method(), uri), uri.query()) {
+            (None, Some(query)) => {
+                let mut target = match (uri.scheme_str(), uri.authority()) {
+                    (Some(scheme), Some(authority)) => format!("{scheme}://{authority}"),
+                    _ => String::new(),
+                };
+                target.push_str(uri.path());
+                target.push('?');
+                target.push_str(&canonical_query(query));
+                target
+            }
+            _ => default_target(request.method(), uri),
+        };
+        let head = self.request_head_with(request.method(), &target, request.headers())?;
+        Ok(Self::join_head(head, &self.body_string()))
+    }
+}
+impl<T> HttpParser<'_, T> {
+    /// The request's query pairs, percent-decoded and with `+` read as a space, in order and
+    /// with duplicates kept. A key without `=` gets an empty value; a request without a query
+    /// gives an empty list.
+    pub fn query_params(&self) -> Result<Vec<(String, String)>, ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Err(ParseError::WrongVariant);
+        };
+        let query = request.uri().query().unwrap_or_default();
+        Ok(query.split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(&key.replace('+', " ")), percent_decode(&value.replace('+', " ")))
+            })
+            .collect())
+    }
+}
+/// Decodes `%XX` escapes; a `%` not followed by two hex digits is kept as is, and invalid
+/// UTF-8 is replaced.
+pub(crate) fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escape = bytes.get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| core::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], escape) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+fn canonical_query(query: &str) -> String {
+    let mut pairs = query.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (pair, None),
+        })
+        .collect::<Vec<_>>();
+    pairs.sort();
+    pairs.iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{key}={value}"),
+            None => key.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+/// The path and query as a cache would compare them: percent-encoded, without dot segments,
+/// and with the query pairs sorted.
+pub(crate) fn cache_target(uri: &Uri) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    let path = percent_encode(uri.path(), b":@/");
+    for segment in path.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut target = format!("/{}", segments.join("/"));
+    if let Some(query) = uri.query().filter(|query| !query.is_empty()) {
+        target.push('?');
+        target.push_str(&canonical_query(&percent_encode(query, b":@/?")));
+    }
+    target
+}
+fn normalized_target(uri: &Uri) -> String {
+    let mut target = String::new();
+    if let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) {
+        target.push_str(&format!("{scheme}://{authority}"));
+    } else if let Some(authority) = uri.authority() {
+        target.push_str(authority.as_str());
+    }
+    target.push_str(&percent_encode(uri.path(), b":@/"));
+    if let Some(query) = uri.query() {
+        target.push('?');
+        target.push_str(&percent_encode(query, b":@/?"));
+    }
+    target
+}
+/// Percent-encodes every byte that is not unreserved, a sub-delimiter, or one of `extra`.
+/// A `%` that already starts a valid escape is copied through unchanged.
+fn percent_encode(input: &str, extra: &[u8]) -> String {
+    let bytes = input.as_bytes();
+    let mut encoded = String::with_capacity(bytes.len());
+    for (index, &byte) in bytes.iter().enumerate() {
+        let is_escape = byte == b'%'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(index + 2).is_some_and(u8::is_ascii_hexdigit);
+        let is_allowed = byte.is_ascii_alphanumeric()
+            || b"-._~!$&'()*+,;=".contains(&byte)
+            || extra.contains(&byte);
+        if is_escape || is_allowed {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+/// The request target `parse()` writes: the URI as given, except that `CONNECT` always
+/// uses authority-form (`CONNECT host:443 HTTP/1.1`) and `OPTIONS *` stays asterisk-form.
+pub(crate) fn default_target(method: &Method, uri: &Uri) -> String {
+    match fixed_target(method, uri) {
+        Some(authority) => authority.to_string(),
+        None => uri.to_string(),
+    }
+}
+/// Writes `default_target` without allocating.
+pub(crate) fn write_target(out: &mut impl fmt::Write, method: &Method, uri: &Uri) -> fmt::Result {
+    match fixed_target(method, uri) {
+        Some(target) => out.write_str(target),
+        None => write!(out, "{uri}"),
+    }
+}
+/// Targets that ignore the requested `UriForm` and are never percent-encoded.
+fn fixed_target<'u>(method: &Method, uri: &'u Uri) -> Option<&'u str> {
+    match *method {
+        Method::CONNECT => uri.authority().map(|authority| authority.as_str()),
+        Method::OPTIONS if uri.path() == "*" && uri.authority().is_none() => Some("*"),
+        _ => None,
+    }
+}
+pub(crate) fn origin_form(uri: &Uri) -> String {
+    let path = match uri.path() {
+        "" => "/",
+        path => path,
+    };
+    match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    }
+}
+pub(crate) fn host_of(uri: &Uri) -> Option<&str> {
+    let authority = uri.authority()?.as_str();
+    authority.rsplit('@').next()
+}
+#[cfg(test)]
+mod tests {
+    use super::{canonical_query, percent_decode, percent_encode};
+    use crate::{HttpParser, ParseError, UriForm};
+    use http::{header, Method, Request, Response, Version};
+
+    fn request(uri: &str) -> Request<&'static str> {
+        Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .version(Version::HTTP_11)
+            .body("")
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_request_origin_form() {
+        let request = request("http://user:pw@example.com:8080/path?q=1");
+        let request = HttpParser::from_request(&request).parse_request_form(UriForm::Origin).unwrap();
+        assert_eq!(request, "GET /path?q=1 HTTP/1.1\r\nhost:example.com:8080\r\n\r\n");
+    }
+    #[test]
+    fn parse_request_origin_form_keeps_host() {
+        let mut request = request("http://example.com");
+        request.headers_mut().insert(header::HOST, "other.example".parse().unwrap());
+        let request = HttpParser::from_request(&request).parse_request_form(UriForm::Origin).unwrap();
+        assert_eq!(request, "GET / HTTP/1.1\r\nhost:other.example\r\n\r\n");
+    }
+    #[test]
+    fn parse_request_absolute_form() {
+        let request = request("http://example.com/path?q=1");
+        let request = HttpParser::from_request(&request).parse_request_form(UriForm::Absolute).unwrap();
+        assert_eq!(request, "GET http://example.com/path?q=1 HTTP/1.1\r\n\r\n");
+    }
+    #[test]
+    fn percent_encode_escapes_unsafe_characters() {
+        assert_eq!(percent_encode("/a b/c", b":@/"), "/a%20b/c");
+        assert_eq!(percent_encode("/café", b":@/"), "/caf%C3%A9");
+        assert_eq!(percent_encode("/caf%C3%A9", b":@/"), "/caf%C3%A9");
+        assert_eq!(percent_encode("/100%", b":@/"), "/100%25");
+        assert_eq!(percent_encode("q=a?b", b":@/?"), "q=a?b");
+    }
+    #[test]
+    fn parse_request_normalized_encodes_path_and_query() {
+        let request = request("http://example.com/say\"hi\"/{id}?q={x}&next=/a?b");
+        let request = HttpParser::from_request(&request).parse_request_normalized().unwrap();
+        assert_eq!(
+            request,
+            "GET http://example.com/say%22hi%22/%7Bid%7D?q=%7Bx%7D&next=/a?b HTTP/1.1\r\n\r\n"
+        );
+    }
+    #[test]
+    fn parse_request_normalized_keeps_encoded_path() {
+        let request = request("/already%20encoded?x=%2F");
+        let request = HttpParser::from_request(&request).parse_request_normalized().unwrap();
+        assert_eq!(request, "GET /already%20encoded?x=%2F HTTP/1.1\r\n\r\n");
+    }
+    #[test]
+    fn parse_request_form_connect() {
+        let mut request = request("https://example.com:443/");
+        *request.method_mut() = Method::CONNECT;
+        let parser = HttpParser::from_request(&request);
+        assert_eq!(
+            parser.parse_request_form(UriForm::Absolute).unwrap(),
+            "CONNECT example.com:443 HTTP/1.1\r\n\r\n"
+        );
+        assert_eq!(
+            parser.parse_request_form(UriForm::Origin).unwrap(),
+            "CONNECT example.com:443 HTTP/1.1\r\nhost:example.com:443\r\n\r\n"
+        );
+    }
+    #[test]
+    fn options_asterisk_form_is_verbatim() {
+        let mut request = request("*");
+        *request.method_mut() = Method::OPTIONS;
+        request.headers_mut().insert(header::HOST, "example.com".parse().unwrap());
+        let parser = HttpParser::from_request(&request);
+        let expected = "OPTIONS * HTTP/1.1\r\nhost:example.com\r\n\r\n";
+        assert_eq!(parser.parse().unwrap(), expected);
+        assert_eq!(parser.parse_request_form(UriForm::Origin).unwrap(), expected);
+        assert_eq!(parser.parse_request_form(UriForm::Absolute).unwrap(), expected);
+        assert_eq!(parser.parse_request_normalized().unwrap(), expected);
+    }
+    #[test]
+    fn request_forms_match_parse_for_http09() {
+        let request = Request::builder()
+            .uri("/a%20b?y=2&x=1")
+            .version(Version::HTTP_09)
+            .body("ignored")
+            .unwrap();
+        let parser = HttpParser::from_request(&request);
+        let expected = "GET /a%20b?y=2&x=1\r\n";
+        assert_eq!(parser.parse().unwrap(), expected);
+        assert_eq!(parser.parse_request_form(UriForm::Origin).unwrap(), expected);
+        assert_eq!(parser.parse_request_normalized().unwrap(), expected);
+        assert_eq!(parser.parse_request_canonical_query().unwrap(), "GET /a%20b?x=1&y=2\r\n");
+    }
+    #[test]
+    fn canonical_query_sorts_pairs() {
+        assert_eq!(canonical_query("b=2&a=1&a=0"), "a=0&a=1&b=2");
+        assert_eq!(canonical_query("z&a=&flag"), "a=&flag&z");
+    }
+    #[test]
+    fn parse_request_canonical_query_sorts_request_line() {
+        let signed = request("http://example.com/list?b=2&a=1&a=0");
+        assert_eq!(
+            HttpParser::from_request(&signed).parse_request_canonical_query().unwrap(),
+            "GET http://example.com/list?a=0&a=1&b=2 HTTP/1.1\r\n\r\n"
+        );
+        let plain = request("/plain");
+        assert_eq!(
+            HttpParser::from_request(&plain).parse_request_canonical_query().unwrap(),
+            "GET /plain HTTP/1.1\r\n\r\n"
+        );
+    }
+    #[test]
+    fn query_params_decodes_pairs_in_order() {
+        let pairs = |uri: &str| HttpParser::from_request(&request(uri)).query_params().unwrap();
+        let expected = [("a", "1"), ("a", "2"), ("b", "")].map(|(key, value)| (key.to_string(), value.to_string()));
+        assert_eq!(pairs("/search?a=1&a=2&b"), expected);
+        assert_eq!(
+            pairs("/search?q=hello+world&path=%2Fhome%2F&bad=100%"),
+            [("q", "hello world"), ("path", "/home/"), ("bad", "100%")].map(|(key, value)| (key.to_string(), value.to_string()))
+        );
+        assert!(pairs("/search").is_empty());
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        assert_eq!(percent_decode("%+5%-1"), "%+5%-1");
+
+        let response = Response::builder().body("").unwrap();
+        assert_eq!(HttpParser::from_response(&response).query_params().unwrap_err(), ParseError::WrongVariant);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{decode, framing, HttpParser, Message, ParseError};
+use http::{header, Method, Version};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+impl<T> HttpParser<'_, T> {
+    pub(crate) fn check_host(&self) -> Result<(), ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Ok(());
+        };
+        if request.version() == Version::HTTP_11 && !request.headers().contains_key(header::HOST) {
+            return Err(ParseError::MissingHost);
+        }
+        Ok(())
+    }
+}
+impl<T> HttpParser<'_, T> {
+    /// Rejects a message that carries both `Content-Length` and `Transfer-Encoding`, since
+    /// peers that honor different headers disagree on where it ends (request smuggling).
+    pub fn check_framing(&self) -> Result<(), ParseError> {
+        let headers = self.headers();
+        if headers.contains_key(header::CONTENT_LENGTH) && headers.contains_key(header::TRANSFER_ENCODING) {
+            return Err(ParseError::ConflictingFraming);
+        }
+        Ok(())
+    }
+    /// Runs every pre-send check that applies to this message and reports all violations at once:
+    /// a supported version, `Host` on HTTP/1.1 requests, unambiguous framing,
+    /// and a request target that fits the method (authority for `CONNECT`, `*` only for `OPTIONS`).
+    pub fn validate(&self) -> Result<(), Vec<ParseError>> {
+        let mut errors = Vec::new();
+        errors.extend(self.parse_version().err());
+        errors.extend(self.check_host().err());
+        errors.extend(self.check_framing().err());
+        errors.extend(self.check_target().err());
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+    fn check_target(&self) -> Result<(), ParseError> {
+        let Message::Request(request) = &self.message else {
+            return Ok(());
+        };
+        let uri = request.uri();
+        let fits = match *request.method() {
+            Method::CONNECT => uri.authority().is_some(),
+            Method::OPTIONS => true,
+            _ => uri.path() != "*",
+        };
+        if !fits {
+            return Err(ParseError::InvalidUri(uri.to_string()));
+        }
+        Ok(())
+    }
+}
+impl<T: AsRef<[u8]>> HttpParser<'_, T> {
+    /// Pre-send check that every `Content-Length` header matches the body actually written.
+    /// A message without `Content-Length` passes, and so do 1xx, 204 and 304 responses, whose
+    /// `Content-Length` describes a body that is never sent.
+    pub fn check_content_length(&self) -> Result<(), ParseError> {
+        if let Message::Response(response) = &self.message && framing::is_bodiless(response.status()) {
+            return Ok(());
+        }
+        decode::check_declared_length(self.headers(), self.body_bytes().len())
+    }
+}
+impl<T: Display> HttpParser<'_, T> {
+    /// Like `parse()`, but requires a `Host` header on HTTP/1.1 requests and rejects
+    /// conflicting framing headers.
+    pub fn parse_strict(&self) -> Result<String, ParseError> {
+        self.check_host()?;
+        self.check_framing()?;
+        self.parse()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{HttpParser, ParseError};
+    use http::{header, Request, Response, StatusCode, Version};
+
+    #[test]
+    fn parse_strict_requires_host_for_http_11() {
+        let request = Request::builder()
+            .uri("/")
+            .version(Version::HTTP_11)
+            .header(header::HOST, "localhost")
+            .body("")
+            .unwrap();
+        assert!(HttpParser::from_request(&request).parse_strict().is_ok());
+
+        let request = Request::builder().uri("/").version(Version::HTTP_11).body("").unwrap();
+        assert_eq!(HttpParser::from_request(&request).parse_strict().unwrap_err(), ParseError::MissingHost);
+
+        let request = Request::builder().uri("/").version(Version::HTTP_10).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).parse_strict().unwrap(),
+            "GET / HTTP/1.0\r\n\r\n"
+        );
+    }
+    #[test]
+    fn check_content_length_compares_body() {
+        let response = |length: Option<&str>| {
+            let mut response = Response::builder().status(StatusCode::OK);
+            if let Some(length) = length {
+                response = response.header(header::CONTENT_LENGTH, length);
+            }
+            response.body("hello").unwrap()
+        };
+        let matching = response(Some("5"));
+        assert_eq!(HttpParser::from_response(&matching).check_content_length(), Ok(()));
+        let short = response(Some("3"));
+        assert_eq!(
+            HttpParser::from_response(&short).check_content_length(),
+            Err(ParseError::ContentLengthMismatch { declared: 3, actual: 5 })
+        );
+        let missing = response(None);
+        assert_eq!(HttpParser::from_response(&missing).check_content_length(), Ok(()));
+        let not_modified = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::CONTENT_LENGTH, "42")
+            .body("")
+            .unwrap();
+        assert_eq!(HttpParser::from_response(&not_modified).check_content_length(), Ok(()));
+    }
+    #[test]
+    fn check_framing_rejects_smuggling() {
+        let request = |headers: &[(header::HeaderName, &str)]| {
+            let mut request = Request::builder().method("POST").uri("/").header(header::HOST, "localhost");
+            for (name, value) in headers {
+                request = request.header(name, *value);
+            }
+            request.body("").unwrap()
+        };
+        let both = request(&[(header::CONTENT_LENGTH, "0"), (header::TRANSFER_ENCODING, "chunked")]);
+        assert_eq!(HttpParser::from_request(&both).check_framing(), Err(ParseError::ConflictingFraming));
+        assert_eq!(HttpParser::from_request(&both).parse_strict(), Err(ParseError::ConflictingFraming));
+
+        let length = request(&[(header::CONTENT_LENGTH, "0")]);
+        assert!(HttpParser::from_request(&length).parse_strict().is_ok());
+        let chunked = request(&[(header::TRANSFER_ENCODING, "chunked")]);
+        assert!(HttpParser::from_request(&chunked).parse_strict().is_ok());
+    }
+    #[test]
+    fn validate_reports_every_violation() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .version(Version::HTTP_11)
+            .header(header::CONTENT_LENGTH, "0")
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .body("")
+            .unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).validate(),
+            Err(vec![ParseError::MissingHost, ParseError::ConflictingFraming])
+        );
+
+        let request = Request::builder().method("CONNECT").uri("/").version(Version::HTTP_10).body("").unwrap();
+        assert_eq!(
+            HttpParser::from_request(&request).validate(),
+            Err(vec![ParseError::InvalidUri(String::from("/"))])
+        );
+        let request = Request::builder()
+            .method("CONNECT")
+            .uri("example.com:443")
+            .header(header::HOST, "example.com:443")
+            .body("")
+            .unwrap();
+        assert_eq!(HttpParser::from_request(&request).validate(), Ok(()));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::ParseError;
+use alloc::string::{String, ToString};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use http::{header, Method, Request, Response, StatusCode, Version};
+use sha1::{Digest, Sha1};
+
+/// The GUID RFC 6455 §1.3 appends to the client key before hashing.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Builds the RFC 6455 client handshake for `ws://{host}{path}`; `key` is sent verbatim.
+pub fn websocket_upgrade_request(host: &str, path: &str, key: &str) -> Result<Request<String>, ParseError> {
+    Request::builder()
+        .method(Method::GET)
+        .uri(path)
+        .version(Version::HTTP_11)
+        .header(header::HOST, host)
+        .header(header::UPGRADE, "websocket")
+        .header(header::CONNECTION, "Upgrade")
+        .header(header::SEC_WEBSOCKET_KEY, key)
+        .header(header::SEC_WEBSOCKET_VERSION, "13")
+        .body(String::new())
+        .map_err(|error| ParseError::InvalidMessage(error.to_string()))
+}
+/// The `Sec-WebSocket-Accept` value answering `client_key`: base64(SHA-1(key + GUID)).
+pub fn websocket_accept_key(client_key: &str) -> String {
+    let mut sha1 = Sha1::new();
+    sha1.update(client_key.trim().as_bytes());
+    sha1.update(WEBSOCKET_GUID.as_bytes());
+    STANDARD.encode(sha1.finalize())
+}
+/// The server's `101 Switching Protocols` handshake; get `accept_key` from `websocket_accept_key`.
+/// Fallible like `websocket_upgrade_request`, since a caller-supplied key may not be a valid header
+/// value. The body is an empty `String` rather than `()`, which has no `Display` for `parse()`.
+pub fn switching_protocols_response(accept_key: &str) -> Result<Response<String>, ParseError> {
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .version(Version::HTTP_11)
+        .header(header::UPGRADE, "websocket")
+        .header(header::CONNECTION, "Upgrade")
+        .header(header::SEC_WEBSOCKET_ACCEPT, accept_key)
+        .body(String::new())
+        .map_err(|error| ParseError::InvalidMessage(error.to_string()))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{switching_protocols_response, websocket_accept_key, websocket_upgrade_request, HttpParser, ParseError};
+    use http::header;
+
+    #[test]
+    fn websocket_upgrade_request_headers() {
+        let request = websocket_upgrade_request("example.com", "/chat", "dGhlIHNhbXBsZSBub25jZQ==").unwrap();
+        let headers = request.headers();
+        assert_eq!(headers[header::UPGRADE], "websocket");
+        assert_eq!(headers[header::CONNECTION], "Upgrade");
+        assert_eq!(headers[header::SEC_WEBSOCKET_KEY], "dGhlIHNhbXBsZSBub25jZQ==");
+        assert_eq!(headers[header::SEC_WEBSOCKET_VERSION], "13");
+        assert_eq!(
+            HttpParser::from_request(&request).parse_strict().unwrap(),
+            "GET /chat HTTP/1.1\r\nhost:example.com\r\nupgrade:websocket\r\nconnection:Upgrade\r\n\
+             sec-websocket-key:dGhlIHNhbXBsZSBub25jZQ==\r\nsec-websocket-version:13\r\n\r\n"
+        );
+    }
+    #[test]
+    fn websocket_upgrade_request_rejects_bad_input() {
+        let error = websocket_upgrade_request("example.com", "/chat", "bad\r\nkey").unwrap_err();
+        assert!(matches!(error, ParseError::InvalidMessage(_)));
+    }
+    #[test]
+    fn switching_protocols_response_uses_accept_key() {
+        let accept_key = websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ==");
+        assert_eq!(accept_key, "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        let response = switching_protocols_response(&accept_key).unwrap();
+        assert_eq!(
+            HttpParser::from_response(&response).parse().unwrap(),
+            "HTTP/1.1 101 Switching Protocols\r\nupgrade:websocket\r\nconnection:Upgrade\r\n\
+             sec-websocket-accept:s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n\r\n"
+        );
+    }
+    #[test]
+    fn switching_protocols_response_rejects_bad_accept_key() {
+        let error = switching_protocols_response("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\nx-injected: 1").unwrap_err();
+        assert!(matches!(error, ParseError::InvalidMessage(_)));
+    }
+}